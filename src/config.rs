@@ -0,0 +1,205 @@
+use super::metadata::model::{CdsType, EdmType, Element, Model, Property};
+use super::renderers::facet_arguments;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const CONFIG_FILES: [&str; 2] = ["cim.toml", "cim.json"];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+  types: BTreeMap<String, String>,
+  entities: BTreeMap<String, BTreeMap<String, String>>,
+  properties: BTreeMap<String, String>,
+}
+
+impl Config {
+  pub fn from_file(path: &Path) -> Result<Self, Box<dyn Error>> {
+    let source = fs::read_to_string(path).map_err(|error| {
+      io::Error::new(
+        error.kind(),
+        format!("Failed to read config {}, {}", path.display(), error),
+      )
+    })?;
+    let config = if path
+      .extension()
+      .is_some_and(|extension| extension == "json")
+    {
+      serde_json::from_str::<Config>(&source).map_err(|error| error.to_string())
+    } else {
+      toml::from_str::<Config>(&source).map_err(|error| error.to_string())
+    };
+    let config = config.map_err(|error| {
+      io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid config {}, {}", path.display(), error),
+      )
+    })?;
+    config.validate(path)?;
+    Ok(config)
+  }
+
+  pub fn discover(path: Option<&str>, directory: &Path) -> Result<Self, Box<dyn Error>> {
+    if let Some(path) = path {
+      return Self::from_file(Path::new(path));
+    }
+    for file in CONFIG_FILES {
+      let path = directory.join(file);
+      if path.is_file() {
+        return Self::from_file(&path);
+      }
+    }
+    Ok(Self::default())
+  }
+
+  pub fn with_type(mut self, edm_type: &str, cds_type: &str) -> Self {
+    self.types.insert(edm_type.to_owned(), cds_type.to_owned());
+    self
+  }
+
+  pub fn with_entity_type(mut self, entity: &str, edm_type: &str, cds_type: &str) -> Self {
+    self
+      .entities
+      .entry(entity.to_owned())
+      .or_default()
+      .insert(edm_type.to_owned(), cds_type.to_owned());
+    self
+  }
+
+  pub fn with_property_type(mut self, property: &str, cds_type: &str) -> Self {
+    self
+      .properties
+      .insert(property.to_owned(), cds_type.to_owned());
+    self
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.types.is_empty() && self.entities.is_empty() && self.properties.is_empty()
+  }
+
+  pub fn apply(&self, model: &mut Model) {
+    if self.is_empty() {
+      return;
+    }
+    for schema in model.schemas_mut() {
+      let namespace = schema.namespace().to_owned();
+      for entity_type in schema.entity_types_mut() {
+        let owner = entity_type.name().to_owned();
+        for element in entity_type.elements_mut() {
+          if let Element::Property(property) = element {
+            self.apply_to_property(&namespace, &owner, property);
+          }
+        }
+      }
+      for complex_type in schema.complex_types_mut() {
+        let owner = complex_type.name().to_owned();
+        for property in complex_type.properties_mut() {
+          self.apply_to_property(&namespace, &owner, property);
+        }
+      }
+    }
+  }
+
+  fn validate(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+    let edm_types = self
+      .types
+      .keys()
+      .chain(self.entities.values().flat_map(|types| types.keys()));
+    for edm_type in edm_types {
+      if EdmType::from_name(&edm_type_name(edm_type)).is_none() {
+        return Err(Box::new(io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!(
+            "Invalid config {}, unknown Edm type '{}'",
+            path.display(),
+            edm_type
+          ),
+        )));
+      }
+    }
+    let cds_types = self
+      .types
+      .values()
+      .chain(self.entities.values().flat_map(|types| types.values()))
+      .chain(self.properties.values());
+    for cds_type in cds_types {
+      if CdsType::parse(cds_type).is_none() {
+        return Err(Box::new(io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!(
+            "Invalid config {}, invalid CDS type '{}'",
+            path.display(),
+            cds_type
+          ),
+        )));
+      }
+    }
+    if let Some(property) = self.properties.keys().find(|key| !key.contains('.')) {
+      return Err(Box::new(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+          "Invalid config {}, property '{}' should be given as Entity.Property",
+          path.display(),
+          property
+        ),
+      )));
+    }
+    Ok(())
+  }
+
+  fn apply_to_property(&self, namespace: &str, owner: &str, property: &mut Property) {
+    let qualified_owner = format!("{}.{}", namespace, owner);
+    let owners = [owner, qualified_owner.as_str()];
+    let property_override = owners.iter().find_map(|owner| {
+      self
+        .properties
+        .get(&format!("{}.{}", owner, property.name()))
+    });
+    if let Some(cds_type) = property_override {
+      if let Some(cds_type) = CdsType::parse(cds_type) {
+        set_cds_type(property, cds_type);
+      }
+      return;
+    }
+    let edm_type = match (property.edm_type(), property.original_type()) {
+      (Some(edm_type), None) => edm_type,
+      _ => return,
+    };
+    let mappings = owners
+      .iter()
+      .filter_map(|owner| self.entities.get(*owner))
+      .chain(std::iter::once(&self.types));
+    for mapping in mappings {
+      let cds_type = mapping
+        .iter()
+        .find(|(name, _)| edm_type_name(name) == edm_type.name());
+      if let Some((_, cds_type)) = cds_type {
+        if let Some(cds_type) = CdsType::parse(cds_type) {
+          set_cds_type(property, cds_type);
+        }
+        return;
+      }
+    }
+  }
+}
+
+fn set_cds_type(property: &mut Property, cds_type: CdsType) {
+  if !cds_type.arguments().is_empty() {
+    property.set_cds_type(cds_type);
+    return;
+  }
+  let arguments = facet_arguments(cds_type.name(), property.facets());
+  property.set_cds_type(cds_type.with_arguments(arguments));
+}
+
+fn edm_type_name(name: &str) -> String {
+  if name.starts_with("Edm.") {
+    name.to_owned()
+  } else {
+    format!("Edm.{}", name)
+  }
+}
@@ -0,0 +1,559 @@
+use std::collections::HashMap;
+
+const GENERATED_BEGIN: &str =
+  "// ---- generated by cim, changes up to the end marker are overwritten ----";
+const GENERATED_END: &str = "// ---- end of generated content ----";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+  Ident(String),
+  Punct(char),
+  Literal,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Statement {
+  Definition {
+    name: String,
+    elements: Vec<String>,
+  },
+  Extension {
+    annotate: bool,
+    target: String,
+    element: Option<String>,
+    elements: Vec<String>,
+    line: usize,
+  },
+}
+
+pub fn wrap_generated(generated: &str) -> String {
+  format!("{}\n{}{}\n", GENERATED_BEGIN, generated, GENERATED_END)
+}
+
+pub fn merge_generated(existing: &str, generated: &str) -> Option<String> {
+  if existing.matches(GENERATED_BEGIN).count() != 1 || existing.matches(GENERATED_END).count() != 1
+  {
+    return None;
+  }
+  let begin = existing.find(GENERATED_BEGIN)?;
+  let end = existing[begin..].find(GENERATED_END)? + begin + GENERATED_END.len();
+  let newline = if existing[begin + GENERATED_BEGIN.len()..].starts_with("\r\n") {
+    "\r\n"
+  } else {
+    "\n"
+  };
+  let end = existing[end..]
+    .strip_prefix(newline)
+    .map_or(end, |_| end + newline.len());
+  Some(format!(
+    "{}{}{}",
+    &existing[..begin],
+    wrap_generated(generated).replace('\n', newline),
+    &existing[end..]
+  ))
+}
+
+pub fn stale_extensions(content: &str) -> Vec<(usize, String)> {
+  let statements = parse(&tokenize(content));
+  let mut definitions = HashMap::new();
+  let mut extended = HashMap::new();
+  for statement in statements.iter() {
+    match statement {
+      Statement::Definition { name, elements } => {
+        definitions.insert(name.as_str(), elements);
+      }
+      Statement::Extension {
+        annotate: false,
+        target,
+        elements,
+        ..
+      } => {
+        let unqualified = target.rsplit('.').next().unwrap_or(target);
+        extended
+          .entry(unqualified)
+          .or_insert_with(Vec::new)
+          .extend(elements.iter());
+      }
+      Statement::Extension { .. } => (),
+    }
+  }
+  let mut problems = Vec::new();
+  for statement in statements.iter() {
+    let (annotate, target, element, elements, line) = match statement {
+      Statement::Extension {
+        annotate,
+        target,
+        element,
+        elements,
+        line,
+      } => (*annotate, target, element, elements, line),
+      Statement::Definition { .. } => continue,
+    };
+    let keyword = if annotate { "annotate" } else { "extend" };
+    let unqualified = target.rsplit('.').next().unwrap_or(target);
+    let defined = definitions
+      .get(target.as_str())
+      .or_else(|| definitions.get(unqualified));
+    let defined = match defined {
+      Some(defined) => defined,
+      None => {
+        problems.push((
+          *line,
+          format!(
+            "{} {} refers to a definition that is no longer generated",
+            keyword, target
+          ),
+        ));
+        continue;
+      }
+    };
+    let stale = element
+      .iter()
+      .chain(elements.iter().filter(|_| annotate))
+      .filter(|element| {
+        !defined.contains(element)
+          && !extended
+            .get(unqualified)
+            .is_some_and(|extended| extended.contains(element))
+      });
+    for element in stale {
+      problems.push((
+        *line,
+        format!(
+          "{} {} refers to element {} that is no longer generated",
+          keyword, target, element
+        ),
+      ));
+    }
+    let clashing = elements
+      .iter()
+      .filter(|element| !annotate && defined.contains(element));
+    for element in clashing {
+      problems.push((
+        *line,
+        format!(
+          "{} {} adds element {} that is generated now",
+          keyword, target, element
+        ),
+      ));
+    }
+  }
+  problems
+}
+
+fn tokenize(source: &str) -> Vec<(Token, usize)> {
+  let chars: Vec<char> = source.chars().collect();
+  let mut tokens = Vec::new();
+  let mut line = 1;
+  let mut position = 0;
+  while position < chars.len() {
+    let current = chars[position];
+    let next = chars.get(position + 1).copied();
+    if current == '\n' {
+      line += 1;
+      position += 1;
+    } else if current.is_whitespace() {
+      position += 1;
+    } else if current == '/' && next == Some('/') {
+      while position < chars.len() && chars[position] != '\n' {
+        position += 1;
+      }
+    } else if current == '/' && next == Some('*') {
+      position += 2;
+      while position < chars.len()
+        && !(chars[position] == '*' && chars.get(position + 1) == Some(&'/'))
+      {
+        if chars[position] == '\n' {
+          line += 1;
+        }
+        position += 1;
+      }
+      position += 2;
+    } else if current == '\'' || current == '"' || current == '`' {
+      let start_line = line;
+      position += 1;
+      while position < chars.len() {
+        if chars[position] == current && chars.get(position + 1) == Some(&current) {
+          position += 2;
+        } else if chars[position] == current {
+          break;
+        } else {
+          if chars[position] == '\n' {
+            line += 1;
+          }
+          position += 1;
+        }
+      }
+      position += 1;
+      tokens.push((Token::Literal, start_line));
+    } else if current == '!' && next == Some('[') {
+      let mut ident = String::new();
+      position += 2;
+      while position < chars.len() {
+        if chars[position] == ']' && chars.get(position + 1) == Some(&']') {
+          ident.push(']');
+          position += 2;
+        } else if chars[position] == ']' {
+          break;
+        } else {
+          ident.push(chars[position]);
+          position += 1;
+        }
+      }
+      position += 1;
+      tokens.push((Token::Ident(ident), line));
+    } else if current.is_alphanumeric() || current == '_' || current == '$' {
+      let mut ident = String::new();
+      while position < chars.len()
+        && (chars[position].is_alphanumeric() || chars[position] == '_' || chars[position] == '$')
+      {
+        ident.push(chars[position]);
+        position += 1;
+      }
+      if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        tokens.push((Token::Literal, line));
+      } else {
+        tokens.push((Token::Ident(ident), line));
+      }
+    } else {
+      tokens.push((Token::Punct(current), line));
+      position += 1;
+    }
+  }
+  tokens
+}
+
+fn parse(tokens: &[(Token, usize)]) -> Vec<Statement> {
+  let mut statements = Vec::new();
+  let mut position = 0;
+  while position < tokens.len() {
+    let (token, line) = &tokens[position];
+    match token {
+      Token::Punct('@') => position = skip_annotation(tokens, position),
+      Token::Ident(keyword) if keyword == "define" => position += 1,
+      Token::Ident(keyword) if keyword == "extend" || keyword == "annotate" => {
+        let (statement, end) = parse_extension(tokens, position, keyword == "annotate", *line);
+        statements.extend(statement);
+        position = end;
+      }
+      Token::Ident(keyword) if matches!(keyword.as_str(), "entity" | "type" | "aspect") => {
+        position += 1;
+        let name = parse_name(tokens, &mut position);
+        while position < tokens.len() && !is_punct(tokens, position, '{') {
+          if is_punct(tokens, position, ';') {
+            break;
+          }
+          position = skip_token(tokens, position);
+        }
+        let mut elements = Vec::new();
+        if is_punct(tokens, position, '{') {
+          let (block, end) = parse_elements(tokens, position);
+          elements = block;
+          position = skip_semicolon(tokens, end);
+        } else {
+          position = skip_statement(tokens, position);
+        }
+        if let Some(name) = name {
+          statements.push(Statement::Definition { name, elements });
+        }
+      }
+      _ => position = skip_statement(tokens, position),
+    }
+  }
+  statements
+}
+
+fn parse_extension(
+  tokens: &[(Token, usize)],
+  mut position: usize,
+  annotate: bool,
+  line: usize,
+) -> (Option<Statement>, usize) {
+  position += 1;
+  if let (Some(Token::Ident(kind)), Some(Token::Ident(name))) = (
+    tokens.get(position).map(|(token, _)| token),
+    tokens.get(position + 1).map(|(token, _)| token),
+  ) {
+    let kinds = ["entity", "type", "aspect", "projection"];
+    if !annotate && kinds.contains(&kind.as_str()) && name != "with" {
+      position += 1;
+    }
+  }
+  let target = parse_name(tokens, &mut position);
+  let mut element = None;
+  if is_punct(tokens, position, ':') {
+    position += 1;
+    element = parse_name(tokens, &mut position);
+  }
+  if is_ident(tokens, position, "with") {
+    position += 1;
+  }
+  let mut elements = Vec::new();
+  while position < tokens.len() {
+    if is_punct(tokens, position, '@') {
+      position = skip_annotation(tokens, position);
+    } else if is_ident(tokens, position, "actions") {
+      position = skip_semicolon(tokens, skip_token(tokens, position + 1));
+      break;
+    } else if is_punct(tokens, position, '{') {
+      let (block, end) = parse_elements(tokens, position);
+      elements.extend(block);
+      position = skip_semicolon(tokens, end);
+      break;
+    } else if is_punct(tokens, position, ';') {
+      position += 1;
+      break;
+    } else if matches!(tokens[position].0, Token::Ident(_)) || is_punct(tokens, position, ',') {
+      position += 1;
+    } else {
+      break;
+    }
+  }
+  let statement = target.map(|target| Statement::Extension {
+    annotate,
+    target,
+    element,
+    elements,
+    line,
+  });
+  (statement, position)
+}
+
+fn parse_elements(tokens: &[(Token, usize)], mut position: usize) -> (Vec<String>, usize) {
+  let mut elements = Vec::new();
+  position += 1;
+  while position < tokens.len() && !is_punct(tokens, position, '}') {
+    while is_punct(tokens, position, '@') {
+      position = skip_annotation(tokens, position);
+    }
+    while ["key", "virtual", "masked", "element"]
+      .iter()
+      .any(|modifier| is_ident(tokens, position, modifier))
+    {
+      position += 1;
+    }
+    if let Some((Token::Ident(name), _)) = tokens.get(position) {
+      elements.push(name.clone());
+    }
+    while position < tokens.len()
+      && !is_punct(tokens, position, ';')
+      && !is_punct(tokens, position, '}')
+    {
+      position = skip_token(tokens, position);
+    }
+    if is_punct(tokens, position, ';') {
+      position += 1;
+    }
+  }
+  (elements, position + 1)
+}
+
+fn parse_name(tokens: &[(Token, usize)], position: &mut usize) -> Option<String> {
+  let mut name = match tokens.get(*position) {
+    Some((Token::Ident(ident), _)) => ident.clone(),
+    _ => return None,
+  };
+  *position += 1;
+  while is_punct(tokens, *position, '.') {
+    match tokens.get(*position + 1) {
+      Some((Token::Ident(ident), _)) => {
+        name.push('.');
+        name.push_str(ident);
+        *position += 2;
+      }
+      _ => break,
+    }
+  }
+  Some(name)
+}
+
+fn skip_annotation(tokens: &[(Token, usize)], mut position: usize) -> usize {
+  position += 1;
+  if is_punct(tokens, position, '(') {
+    return skip_token(tokens, position);
+  }
+  parse_name(tokens, &mut position);
+  if is_punct(tokens, position, '#') {
+    position += 2;
+  }
+  if !is_punct(tokens, position, ':') {
+    return position;
+  }
+  position += 1;
+  if is_punct(tokens, position, '#') || is_punct(tokens, position, '-') {
+    return position + 2;
+  }
+  if parse_name(tokens, &mut position).is_some() {
+    return position;
+  }
+  skip_token(tokens, position)
+}
+
+fn skip_statement(tokens: &[(Token, usize)], mut position: usize) -> usize {
+  while position < tokens.len() {
+    if is_punct(tokens, position, ';') {
+      return position + 1;
+    }
+    if is_punct(tokens, position, '{') {
+      return skip_semicolon(tokens, skip_token(tokens, position));
+    }
+    position = skip_token(tokens, position);
+  }
+  position
+}
+
+fn skip_semicolon(tokens: &[(Token, usize)], position: usize) -> usize {
+  if is_punct(tokens, position, ';') {
+    position + 1
+  } else {
+    position
+  }
+}
+
+fn skip_token(tokens: &[(Token, usize)], position: usize) -> usize {
+  if !matches!(
+    tokens.get(position),
+    Some((Token::Punct('(' | '[' | '{'), _))
+  ) {
+    return position + 1;
+  }
+  let mut depth = 0;
+  let mut position = position;
+  while position < tokens.len() {
+    match tokens[position].0 {
+      Token::Punct('(' | '[' | '{') => depth += 1,
+      Token::Punct(')' | ']' | '}') => depth -= 1,
+      _ => (),
+    }
+    position += 1;
+    if depth == 0 {
+      break;
+    }
+  }
+  position
+}
+
+fn is_punct(tokens: &[(Token, usize)], position: usize, punct: char) -> bool {
+  matches!(tokens.get(position), Some((Token::Punct(current), _)) if *current == punct)
+}
+
+fn is_ident(tokens: &[(Token, usize)], position: usize, ident: &str) -> bool {
+  matches!(tokens.get(position), Some((Token::Ident(current), _)) if current == ident)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{merge_generated, stale_extensions, wrap_generated, GENERATED_BEGIN, GENERATED_END};
+
+  const GENERATED: &str = "entity Product {\n  key ID: UUID;\n  Name: String(100);\n}\n";
+
+  #[test]
+  fn with_braces_and_keywords_in_strings_and_comments_it_ignores_them() {
+    let content = format!(
+      "{}\n// extend Missing with {{ Old: Integer; }}\n/* annotate Missing with {{\n  Old @title: 'x';\n}} */\nannotate Product with @(title: 'extend Missing with {{ }}', description: \"{{ ; }}\");\nannotate Product with {{\n  Name @title: '}} extend Missing {{';\n}}\n",
+      wrap_generated(GENERATED)
+    );
+
+    assert_eq!(stale_extensions(&content), []);
+  }
+
+  #[test]
+  fn with_nested_braces_it_finds_the_elements() {
+    let content = format!(
+      "{}\nextend entity Product with {{\n  Reviews: Composition of many {{\n    key ID: UUID;\n    Text: String;\n  }};\n  @UI.LineItem: [{{ Value: Rating }}]\n  Rating: Integer;\n}}\n\nannotate Product with {{\n  Reviews @title: 'Reviews';\n  Rating @title: 'Rating';\n  Text @title: 'Text';\n}}\n",
+      wrap_generated(GENERATED)
+    );
+
+    assert_eq!(
+      stale_extensions(&content),
+      [(
+        17,
+        "annotate Product refers to element Text that is no longer generated".to_owned()
+      )]
+    );
+  }
+
+  #[test]
+  fn with_stale_extensions_it_reports_their_lines() {
+    let content = format!(
+      "{}\nextend Product with {{\n  Name: String;\n}}\n\nannotate Category with @title: 'Category';\n",
+      wrap_generated(GENERATED)
+    );
+
+    assert_eq!(
+      stale_extensions(&content),
+      [
+        (
+          8,
+          "extend Product adds element Name that is generated now".to_owned()
+        ),
+        (
+          12,
+          "annotate Category refers to a definition that is no longer generated".to_owned()
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn with_markers_it_replaces_only_the_generated_region() {
+    let existing = format!(
+      "using from './common';\n{}\nextend Product with {{ Rating: Integer; }}\n",
+      wrap_generated("entity Old {}\n")
+    );
+
+    let merged = merge_generated(&existing, GENERATED).unwrap();
+
+    assert_eq!(
+      merged,
+      format!(
+        "using from './common';\n{}\nextend Product with {{ Rating: Integer; }}\n",
+        wrap_generated(GENERATED)
+      )
+    );
+  }
+
+  #[test]
+  fn with_missing_or_duplicated_markers_it_returns_none() {
+    let begin_only = format!("{}\nentity Old {{}}\n", GENERATED_BEGIN);
+    let end_only = format!("entity Old {{}}\n{}\n", GENERATED_END);
+    let reversed = format!("{}\nentity Old {{}}\n{}\n", GENERATED_END, GENERATED_BEGIN);
+    let duplicated = format!(
+      "{}\n{}",
+      wrap_generated("entity Old {}\n"),
+      wrap_generated("entity Old {}\n")
+    );
+    let nested = format!("{}\n{}", GENERATED_BEGIN, wrap_generated("entity Old {}\n"));
+
+    for existing in [
+      "entity Old {}\n",
+      &begin_only,
+      &end_only,
+      &reversed,
+      &duplicated,
+      &nested,
+    ] {
+      assert_eq!(merge_generated(existing, GENERATED), None);
+    }
+  }
+
+  #[test]
+  fn with_crlf_it_keeps_the_line_endings_and_lines() {
+    let existing = wrap_generated("entity Old {}\n").replace('\n', "\r\n")
+      + "\r\nannotate Product with {\r\n  Tags @UI.Hidden;\r\n}\r\n";
+
+    let merged = merge_generated(&existing, GENERATED).unwrap();
+
+    assert_eq!(merged.matches('\n').count(), merged.matches("\r\n").count());
+    assert!(merged.ends_with(
+      "// ---- end of generated content ----\r\n\r\nannotate Product with {\r\n  Tags @UI.Hidden;\r\n}\r\n"
+    ));
+    assert_eq!(
+      stale_extensions(&merged),
+      [(
+        8,
+        "annotate Product refers to element Tags that is no longer generated".to_owned()
+      )]
+    );
+  }
+}
@@ -1,21 +1,804 @@
+mod config;
+mod extensions;
 mod metadata;
+mod naming;
+mod remote;
+pub mod renderers;
 mod xml_tags;
 
 use clap::Parser;
 use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 use xml_tags::xml_rs::XmlTagParser;
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
+pub use config::Config;
+pub use metadata::model;
+pub use metadata::{diff, Change, EntityFilter};
+pub use metadata::{validate, Severity, ValidationError, ValidationIssue};
+pub use metadata::{ParserError, ParserErrorKind};
+pub use naming::{ElementCase, Naming};
+pub use remote::MetadataRequest;
+pub use renderers::{Format, OutputFile, RenderOptions, SqlDialect};
+
+#[derive(Parser, Default)]
+#[clap(author, version, about, long_about = None, subcommand_negates_reqs = true)]
 pub struct Args {
-  #[clap(help = "Metadata file path")]
-  pub path: String,
+  #[clap(subcommand)]
+  pub command: Option<Command>,
+  #[clap(flatten)]
+  pub input: InputArgs,
+  #[clap(long, value_enum, default_value_t, help = "Output format")]
+  pub format: Format,
+  #[clap(
+    long,
+    value_enum,
+    default_value_t,
+    help = "SQL dialect used by --format sql"
+  )]
+  pub dialect: SqlDialect,
+  #[clap(
+    long,
+    value_enum,
+    help = "OData version of the service, detected from the metadata by default"
+  )]
+  pub odata_version: Option<model::ODataVersion>,
+  #[clap(
+    long,
+    default_value_t = 10,
+    help = "Number of rows per entity generated by --format csv"
+  )]
+  pub rows: usize,
+  #[clap(
+    long,
+    default_value_t,
+    help = "Seed of the mock data generated by --format csv"
+  )]
+  pub seed: u64,
+  #[clap(
+    short,
+    long,
+    help = "Output file, or directory when several files are generated"
+  )]
+  pub output: Option<String>,
+  #[clap(long, help = "Generate one output per input instead of a merged model")]
+  pub split: bool,
+  #[clap(long, help = "Overwrite existing output files")]
+  pub force: bool,
+  #[clap(
+    long,
+    help = "Only rewrite the generated region of existing CDS files, keeping hand-written extensions"
+  )]
+  pub incremental: bool,
+  #[clap(
+    long,
+    global = true,
+    help = "Fail on unsupported metadata instead of skipping it with a warning"
+  )]
+  pub strict: bool,
+  #[clap(
+    long,
+    global = true,
+    help = "Only keep entity types matching the glob, e.g. 'Product*'"
+  )]
+  pub include: Vec<String>,
+  #[clap(long, global = true, help = "Drop entity types matching the glob")]
+  pub exclude: Vec<String>,
+  #[clap(
+    long,
+    global = true,
+    help = "Only keep entity types reachable from this one through navigation properties"
+  )]
+  pub reachable_from: Vec<String>,
+  #[clap(
+    long,
+    global = true,
+    help = "Maximum number of navigation steps followed by --reachable-from"
+  )]
+  pub depth: Option<usize>,
+  #[clap(
+    long,
+    global = true,
+    help = "Config file with type mapping overrides, cim.toml or cim.json of the working directory by default"
+  )]
+  pub config: Option<String>,
+  #[clap(
+    long,
+    global = true,
+    help = "Prefix stripped from entity type names, e.g. 'A_'"
+  )]
+  pub strip_prefix: Vec<String>,
+  #[clap(
+    long,
+    global = true,
+    help = "Suffix stripped from entity type names, e.g. 'Type'"
+  )]
+  pub strip_suffix: Vec<String>,
+  #[clap(long, global = true, help = "Use plural entity type names")]
+  pub pluralize: bool,
+  #[clap(
+    long,
+    value_enum,
+    global = true,
+    help = "Case of the generated element names"
+  )]
+  pub element_case: Option<ElementCase>,
+  #[clap(short, long, global = true, help = "Print progress messages to stderr")]
+  pub verbose: bool,
+}
+
+#[derive(clap::Args, Default)]
+pub struct InputArgs {
+  #[clap(
+    required_unless_present = "urls",
+    help = "Metadata file paths, directories of metadata files or - for stdin"
+  )]
+  pub paths: Vec<String>,
+  #[clap(
+    long = "url",
+    help = "Service URL to fetch the $metadata document from"
+  )]
+  pub urls: Vec<String>,
+  #[clap(long, help = "Basic auth credentials for --url as user:password")]
+  pub user: Option<String>,
+  #[clap(long, help = "Bearer token for --url")]
+  pub token: Option<String>,
+  #[clap(
+    short = 'H',
+    long = "header",
+    help = "Additional HTTP header for --url as \"Name: Value\""
+  )]
+  pub headers: Vec<String>,
+  #[clap(long, help = "SAP client requested by --url")]
+  pub sap_client: Option<String>,
+}
+
+#[derive(clap::Subcommand)]
+pub enum Command {
+  #[clap(about = "Import services into a CAP project like cds import does")]
+  Import(ImportArgs),
+  #[clap(about = "Validate metadata documents, failing on problems for CI")]
+  Check(CheckArgs),
+  #[clap(about = "Compare two metadata versions and classify the changes")]
+  Diff(DiffArgs),
+}
+
+#[derive(clap::Args, Default)]
+pub struct ImportArgs {
+  #[clap(flatten)]
+  pub input: InputArgs,
+  #[clap(long, default_value = ".", help = "Directory of the CAP project")]
+  pub project: String,
+  #[clap(
+    long,
+    value_enum,
+    help = "OData version of the service, detected from the metadata by default"
+  )]
+  pub odata_version: Option<model::ODataVersion>,
+  #[clap(long, help = "Overwrite existing service files")]
+  pub force: bool,
+}
+
+#[derive(clap::Args, Default)]
+pub struct CheckArgs {
+  #[clap(flatten)]
+  pub input: InputArgs,
+}
+
+#[derive(clap::Args, Default)]
+pub struct DiffArgs {
+  #[clap(help = "Previous version of the metadata")]
+  pub old: String,
+  #[clap(help = "New version of the metadata")]
+  pub new: String,
+  #[clap(long, value_enum, default_value_t, help = "Output format")]
+  pub format: DiffFormat,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DiffFormat {
+  #[default]
+  Text,
+  Json,
 }
 
 pub fn run(args: Args) -> Result<String, Box<dyn Error>> {
-  let path = args.path;
-  let tag_parser = XmlTagParser::from_file(path)?;
-  let mut parser = metadata::Parser::new(Box::new(tag_parser));
-  let cds = parser.parse()?;
-  Ok(cds)
+  let filter = entity_filter(&args);
+  let naming = naming(&args);
+  match args.command {
+    Some(Command::Import(import_args)) => {
+      let config = Config::discover(args.config.as_deref(), Path::new("."))?;
+      return import(
+        import_args,
+        &filter,
+        &config,
+        &naming,
+        args.strict,
+        args.verbose,
+      );
+    }
+    Some(Command::Check(check_args)) => return check(check_args, args.strict, args.verbose),
+    Some(Command::Diff(diff_args)) => return compare(diff_args, args.strict, args.verbose),
+    None => (),
+  }
+  if args.incremental && (args.format != Format::Cdl || args.output.is_none()) {
+    return Err(Box::new(io::Error::new(
+      io::ErrorKind::InvalidInput,
+      "--incremental needs --format cdl and an --output",
+    )));
+  }
+  if !naming.is_empty() && !matches!(args.format, Format::Cdl | Format::Csn | Format::Csv) {
+    return Err(Box::new(io::Error::new(
+      io::ErrorKind::InvalidInput,
+      "--strip-prefix, --strip-suffix, --pluralize and --element-case only apply to --format cdl, csn and csv",
+    )));
+  }
+  let config = Config::discover(args.config.as_deref(), Path::new("."))?;
+  let inputs = collect_inputs(&args.input)?;
+  let options = RenderOptions::default()
+    .with_dialect(args.dialect)
+    .with_odata_version(args.odata_version)
+    .with_rows(args.rows)
+    .with_seed(args.seed);
+  let renderer = args.format.renderer(&options);
+  let output = args.output.map(PathBuf::from);
+  let mut targets = Vec::new();
+  let mut warnings = Vec::new();
+  if args.split {
+    let mut models = Vec::new();
+    for input in inputs.iter() {
+      models.push((
+        input.stem(),
+        parse_input(input, args.verbose, &mut warnings)?,
+      ));
+    }
+    report_warnings(warnings, args.strict)?;
+    for (stem, mut model) in models {
+      filter.apply(&mut model)?;
+      config.apply(&mut model);
+      naming.apply(&mut model)?;
+      if args.format == Format::Cdl {
+        report_identifiers(&model);
+      }
+      for file in renderer.render_files(&model)? {
+        let path = match file.path() {
+          Some(path) => Path::new(&stem).join(path),
+          None => PathBuf::from(format!("{}.{}", stem, args.format.extension())),
+        };
+        let path = match output.as_ref() {
+          Some(output) => output.join(path),
+          None => path,
+        };
+        if targets.iter().any(|(target, _)| *target == path) {
+          return Err(Box::new(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+              "Output file {} is generated by several inputs",
+              path.display()
+            ),
+          )));
+        }
+        targets.push((path, file));
+      }
+    }
+  } else {
+    let mut model = model::Model::default();
+    for input in inputs.iter() {
+      model.merge(parse_input(input, args.verbose, &mut warnings)?);
+    }
+    report_warnings(warnings, args.strict)?;
+    filter.apply(&mut model)?;
+    config.apply(&mut model);
+    naming.apply(&mut model)?;
+    if args.format == Format::Cdl {
+      report_identifiers(&model);
+    }
+    let mut files = renderer.render_files(&model)?;
+    match (files.len(), output) {
+      (1, None) if files[0].path().is_none() => return Ok(files.remove(0).content().to_owned()),
+      (1, Some(output)) if files[0].path().is_none() => targets.push((output, files.remove(0))),
+      (_, output) => {
+        for file in files {
+          let path = file
+            .path()
+            .unwrap_or_else(|| Path::new("output"))
+            .to_path_buf();
+          let path = match output.as_ref() {
+            Some(output) => output.join(path),
+            None => path,
+          };
+          targets.push((path, file));
+        }
+      }
+    }
+  }
+  if args.incremental {
+    targets = regenerate(targets, args.force, args.strict)?;
+  }
+  write_files(targets, args.force || args.incremental, args.verbose)?;
+  Ok(String::new())
+}
+
+enum Input {
+  Stdin,
+  File(String),
+  Url(MetadataRequest),
+}
+
+impl Input {
+  fn stem(&self) -> String {
+    match self {
+      Input::Stdin => "stdin".to_owned(),
+      Input::File(path) => Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "output".to_owned()),
+      Input::Url(request) => request.service_name(),
+    }
+  }
+
+  fn name(&self) -> String {
+    match self {
+      Input::Stdin => "<stdin>".to_owned(),
+      Input::File(path) => path.clone(),
+      Input::Url(request) => request.metadata_url(),
+    }
+  }
+}
+
+fn import(
+  args: ImportArgs,
+  filter: &EntityFilter,
+  config: &Config,
+  naming: &Naming,
+  strict: bool,
+  verbose: bool,
+) -> Result<String, Box<dyn Error>> {
+  let project = PathBuf::from(&args.project);
+  let package_path = project.join("package.json");
+  let mut package = if package_path.exists() {
+    serde_json::from_str(&fs::read_to_string(&package_path)?)?
+  } else {
+    serde_json::json!({})
+  };
+  let requires = cds_requires(&mut package, &package_path)?;
+  let mut targets = Vec::new();
+  let mut warnings = Vec::new();
+  for input in collect_inputs(&args.input)? {
+    let edmx = read_input(&input, verbose)?;
+    let mut model = parse_source(Some(&input.name()), edmx.clone(), Some(&mut warnings))?;
+    filter.apply(&mut model)?;
+    config.apply(&mut model);
+    naming.apply(&mut model)?;
+    let kind = match args.odata_version.or_else(|| model.version()) {
+      Some(model::ODataVersion::V2) => "odata-v2",
+      Some(model::ODataVersion::V4) => "odata",
+      None => {
+        return Err(Box::new(io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!(
+            "Can't detect the OData version of {}, pass --odata-version",
+            input.stem()
+          ),
+        )))
+      }
+    };
+    let service = input.stem();
+    let model_path = format!("srv/external/{}", service);
+    report_identifiers(&model);
+    let cds = Format::Cdl
+      .renderer(&RenderOptions::default())
+      .render(&model)?;
+    targets.push((
+      project.join(format!("{}.cds", model_path)),
+      OutputFile::new(None, cds),
+    ));
+    targets.push((
+      project.join(format!("{}.edmx", model_path)),
+      OutputFile::new(None, edmx),
+    ));
+    requires.insert(
+      service,
+      serde_json::json!({
+        "kind": kind,
+        "model": model_path,
+      }),
+    );
+  }
+  report_warnings(warnings, strict)?;
+  write_files(targets, args.force, verbose)?;
+  fs::write(
+    &package_path,
+    format!("{}\n", serde_json::to_string_pretty(&package)?),
+  )?;
+  if verbose {
+    eprintln!("Written {}", package_path.display());
+  }
+  Ok(String::new())
+}
+
+fn entity_filter(args: &Args) -> EntityFilter {
+  let mut filter = EntityFilter::default().with_depth(args.depth);
+  for pattern in args.include.iter() {
+    filter = filter.with_include(pattern);
+  }
+  for pattern in args.exclude.iter() {
+    filter = filter.with_exclude(pattern);
+  }
+  for entity_type in args.reachable_from.iter() {
+    filter = filter.with_reachable_from(entity_type);
+  }
+  filter
+}
+
+fn naming(args: &Args) -> Naming {
+  let mut naming = Naming::default()
+    .with_pluralize(args.pluralize)
+    .with_element_case(args.element_case);
+  for prefix in args.strip_prefix.iter() {
+    naming = naming.with_strip_prefix(prefix);
+  }
+  for suffix in args.strip_suffix.iter() {
+    naming = naming.with_strip_suffix(suffix);
+  }
+  naming
+}
+
+fn check(args: CheckArgs, strict: bool, verbose: bool) -> Result<String, Box<dyn Error>> {
+  let inputs = collect_inputs(&args.input)?;
+  let mut errors = 0;
+  let mut warnings = 0;
+  for input in inputs.iter() {
+    let mut parser_warnings = Vec::new();
+    let model = parse_input(input, verbose, &mut parser_warnings)?;
+    for warning in parser_warnings {
+      errors += 1;
+      eprintln!("error: {}\n", warning);
+    }
+    for issue in validate(&model) {
+      match issue.severity() {
+        Severity::Error => errors += 1,
+        Severity::Warning => warnings += 1,
+      }
+      eprintln!("{}: {}\n --> {}\n", issue.severity(), issue, input.name());
+    }
+  }
+  if errors > 0 || (strict && warnings > 0) {
+    return Err(Box::new(ValidationError::new(errors, warnings)));
+  }
+  Ok(format!(
+    "{} input(s) checked, {} warning(s) found\n",
+    inputs.len(),
+    warnings
+  ))
+}
+
+fn compare(args: DiffArgs, strict: bool, verbose: bool) -> Result<String, Box<dyn Error>> {
+  let mut warnings = Vec::new();
+  let old = parse_input(&Input::File(args.old), verbose, &mut warnings)?;
+  let new = parse_input(&Input::File(args.new), verbose, &mut warnings)?;
+  report_warnings(warnings, strict)?;
+  let changes = diff(&old, &new);
+  let breaking = changes.iter().filter(|change| change.is_breaking()).count();
+  let compatible = changes.len() - breaking;
+  match args.format {
+    DiffFormat::Text => {
+      let mut report = String::new();
+      for change in changes.iter() {
+        let compatibility = if change.is_breaking() {
+          "breaking"
+        } else {
+          "compatible"
+        };
+        report.push_str(&format!("{}: {}\n", compatibility, change));
+      }
+      if changes.is_empty() {
+        report.push_str("No changes\n");
+      } else {
+        report.push_str(&format!(
+          "\n{} breaking and {} compatible change(s)\n",
+          breaking, compatible
+        ));
+      }
+      Ok(report)
+    }
+    DiffFormat::Json => {
+      let changes: Vec<_> = changes
+        .iter()
+        .map(|change| {
+          serde_json::json!({
+            "kind": change.code(),
+            "breaking": change.is_breaking(),
+            "owner": change.owner(),
+            "element": change.element(),
+            "old": change.old_value(),
+            "new": change.new_value(),
+            "message": change.to_string(),
+          })
+        })
+        .collect();
+      let report = serde_json::json!({
+        "breaking": breaking,
+        "compatible": compatible,
+        "changes": changes,
+      });
+      Ok(format!("{}\n", serde_json::to_string_pretty(&report)?))
+    }
+  }
+}
+
+fn collect_inputs(args: &InputArgs) -> Result<Vec<Input>, Box<dyn Error>> {
+  let mut inputs = Vec::new();
+  for path in args.paths.iter() {
+    if path == "-" {
+      inputs.push(Input::Stdin);
+      continue;
+    }
+    if !Path::new(path).is_dir() {
+      inputs.push(Input::File(path.clone()));
+      continue;
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path)? {
+      let file = entry?.path();
+      let is_metadata = matches!(
+        file.extension().and_then(|extension| extension.to_str()),
+        Some("xml" | "edmx")
+      );
+      if file.is_file() && is_metadata {
+        files.push(file.to_string_lossy().into_owned());
+      }
+    }
+    if files.is_empty() {
+      return Err(Box::new(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("No metadata files found in {}", path),
+      )));
+    }
+    files.sort();
+    inputs.extend(files.into_iter().map(Input::File));
+  }
+  for url in args.urls.iter() {
+    inputs.push(Input::Url(metadata_request(url, args)?));
+  }
+  Ok(inputs)
+}
+
+fn metadata_request(url: &str, args: &InputArgs) -> Result<MetadataRequest, Box<dyn Error>> {
+  let mut request = MetadataRequest::new(url);
+  if let Some(user) = args.user.as_ref() {
+    let (user, password) = user.split_once(':').unwrap_or((user, ""));
+    request = request.with_basic_auth(user, password);
+  }
+  if let Some(token) = args.token.as_ref() {
+    request = request.with_token(token);
+  }
+  for header in args.headers.iter() {
+    let (name, value) = header.split_once(':').ok_or_else(|| {
+      io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Invalid header {}, expected Name: Value", header),
+      )
+    })?;
+    request = request.with_header(name.trim(), value.trim());
+  }
+  if let Some(sap_client) = args.sap_client.as_ref() {
+    request = request.with_sap_client(sap_client);
+  }
+  Ok(request)
+}
+
+fn parse_input(
+  input: &Input,
+  verbose: bool,
+  warnings: &mut Vec<ParserError>,
+) -> Result<model::Model, Box<dyn Error>> {
+  let source = read_input(input, verbose)?;
+  parse_source(Some(&input.name()), source, Some(warnings))
+}
+
+fn report_warnings(mut warnings: Vec<ParserError>, strict: bool) -> Result<(), Box<dyn Error>> {
+  if strict && warnings.len() == 1 {
+    return Err(Box::new(warnings.remove(0)));
+  }
+  if strict && !warnings.is_empty() {
+    let errors: Vec<_> = warnings.iter().map(|error| error.to_string()).collect();
+    return Err(Box::new(io::Error::new(
+      io::ErrorKind::InvalidData,
+      errors.join("\n\n"),
+    )));
+  }
+  for warning in warnings.iter() {
+    eprintln!("warning: {}\n", warning);
+  }
+  if !warnings.is_empty() {
+    eprintln!(
+      "warning: {} metadata problem(s) skipped or replaced, pass --strict to fail on them",
+      warnings.len()
+    );
+  }
+  Ok(())
+}
+
+fn report_identifiers(model: &model::Model) {
+  for identifier in renderers::quoted_identifiers(model) {
+    eprintln!("note: {}", identifier);
+  }
+}
+
+fn read_input(input: &Input, verbose: bool) -> Result<String, Box<dyn Error>> {
+  let mut edmx = String::new();
+  match input {
+    Input::Stdin => {
+      if verbose {
+        eprintln!("Reading stdin...");
+      }
+      io::stdin()
+        .read_to_string(&mut edmx)
+        .map_err(|error| ParserError::from(error).with_file(Some("<stdin>")))?;
+    }
+    Input::File(path) => {
+      if verbose {
+        eprintln!("Reading {}...", path);
+      }
+      edmx =
+        fs::read_to_string(path).map_err(|error| ParserError::from(error).with_file(Some(path)))?;
+    }
+    Input::Url(request) => {
+      if verbose {
+        eprintln!("Fetching {}...", request.metadata_url());
+      }
+      request.fetch()?.read_to_string(&mut edmx)?;
+    }
+  }
+  Ok(edmx)
+}
+
+fn cds_requires<'a>(
+  package: &'a mut serde_json::Value,
+  package_path: &Path,
+) -> Result<&'a mut serde_json::Map<String, serde_json::Value>, Box<dyn Error>> {
+  let not_an_object = |key: Option<&str>| -> Box<dyn Error> {
+    let message = match key {
+      Some(key) => format!(
+        "{} has a key {} that is not a JSON object",
+        package_path.display(),
+        key
+      ),
+      None => format!("{} is not a JSON object", package_path.display()),
+    };
+    Box::new(io::Error::new(io::ErrorKind::InvalidData, message))
+  };
+  let cds = package
+    .as_object_mut()
+    .ok_or_else(|| not_an_object(None))?
+    .entry("cds")
+    .or_insert_with(|| serde_json::json!({}))
+    .as_object_mut()
+    .ok_or_else(|| not_an_object(Some("cds")))?;
+  cds
+    .entry("requires")
+    .or_insert_with(|| serde_json::json!({}))
+    .as_object_mut()
+    .ok_or_else(|| not_an_object(Some("cds.requires")))
+}
+
+fn regenerate(
+  targets: Vec<(PathBuf, OutputFile)>,
+  force: bool,
+  strict: bool,
+) -> Result<Vec<(PathBuf, OutputFile)>, Box<dyn Error>> {
+  let mut regenerated = Vec::new();
+  let mut problems = Vec::new();
+  for (path, file) in targets {
+    let content = if path.is_file() {
+      let existing = fs::read_to_string(&path)?;
+      match extensions::merge_generated(&existing, file.content()) {
+        Some(content) => content,
+        None if force => extensions::wrap_generated(file.content()),
+        None => {
+          return Err(Box::new(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+            "Output file {} has no generated region or more than one, pass --force to overwrite it",
+            path.display()
+          ),
+          )))
+        }
+      }
+    } else {
+      extensions::wrap_generated(file.content())
+    };
+    for (line, problem) in extensions::stale_extensions(&content) {
+      problems.push(format!("{}:{}: {}", path.display(), line, problem));
+    }
+    let file = OutputFile::new(file.path().map(Path::to_path_buf), content);
+    regenerated.push((path, file));
+  }
+  if strict && !problems.is_empty() {
+    return Err(Box::new(io::Error::new(
+      io::ErrorKind::InvalidData,
+      format!("Stale extensions found\n{}", problems.join("\n")),
+    )));
+  }
+  for problem in problems.iter() {
+    eprintln!("warning: stale extension, {}", problem);
+  }
+  Ok(regenerated)
+}
+
+fn write_files(
+  files: Vec<(PathBuf, OutputFile)>,
+  force: bool,
+  verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+  for (path, _) in files.iter() {
+    if path.is_dir() {
+      return Err(Box::new(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Output file {} is a directory", path.display()),
+      )));
+    }
+    if path.exists() && !force {
+      return Err(Box::new(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!(
+          "Output file {} already exists, pass --force to overwrite it",
+          path.display()
+        ),
+      )));
+    }
+  }
+  for (path, file) in files {
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, file.content())?;
+    if verbose {
+      eprintln!("Written {}", path.display());
+    }
+  }
+  Ok(())
+}
+
+pub fn parse_file(path: String) -> Result<model::Model, Box<dyn Error>> {
+  let source =
+    fs::read_to_string(&path).map_err(|error| ParserError::from(error).with_file(Some(&path)))?;
+  parse_source(Some(&path), source, None)
+}
+
+pub fn parse_reader(mut reader: Box<dyn Read>) -> Result<model::Model, Box<dyn Error>> {
+  let mut source = String::new();
+  reader
+    .read_to_string(&mut source)
+    .map_err(ParserError::from)?;
+  parse_source(None, source, None)
+}
+
+pub fn parse_url(request: &MetadataRequest) -> Result<model::Model, Box<dyn Error>> {
+  let mut source = String::new();
+  request.fetch()?.read_to_string(&mut source)?;
+  parse_source(Some(&request.metadata_url()), source, None)
+}
+
+fn parse_source(
+  file: Option<&str>,
+  source: String,
+  warnings: Option<&mut Vec<ParserError>>,
+) -> Result<model::Model, Box<dyn Error>> {
+  let tag_parser = XmlTagParser::new(Box::new(io::Cursor::new(source.clone())));
+  let mut parser = metadata::Parser::new(Box::new(tag_parser)).with_lenient(warnings.is_some());
+  let model = parser
+    .parse()
+    .map_err(|error| match ParserError::downcast(error) {
+      Ok(error) => Box::new(error.with_source(file, &source)),
+      Err(error) => error,
+    })?;
+  if let Some(warnings) = warnings {
+    for warning in parser.take_warnings() {
+      warnings.push(warning.with_source(file, &source));
+    }
+  }
+  Ok(model)
 }
@@ -1,18 +1,23 @@
 use cim::run;
-use cim::Args;
+use cim::{Args, Command, ValidationError};
 use clap::Parser;
 use std::process::exit;
 
 fn main() {
   let args = Args::parse();
-  println!("CIM {}", env!("CARGO_PKG_VERSION"));
-  println!("Reading {}...", args.path);
+  if args.verbose {
+    eprintln!("CIM {}", env!("CARGO_PKG_VERSION"));
+  }
+  let checking = matches!(args.command, Some(Command::Check(_)));
   match run(args) {
     Ok(cds) => {
       print!("{cds}");
     }
     Err(error) => {
       eprintln!("{error}");
+      if checking && !error.is::<ValidationError>() {
+        exit(2);
+      }
       exit(1);
     }
   }
@@ -1,2 +0,0 @@
-pub mod entity;
-pub mod field;
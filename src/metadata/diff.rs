@@ -0,0 +1,451 @@
+use super::model::{EntityType, Model, NavigationProperty, Property, PropertyType, Scale, Schema};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+  TypeAdded {
+    owner: String,
+  },
+  TypeRemoved {
+    owner: String,
+  },
+  PropertyAdded {
+    owner: String,
+    property: String,
+    nullable: bool,
+  },
+  PropertyRemoved {
+    owner: String,
+    property: String,
+  },
+  KeyChanged {
+    owner: String,
+    old: Vec<String>,
+    new: Vec<String>,
+  },
+  PropertyTypeChanged {
+    owner: String,
+    property: String,
+    old: String,
+    new: String,
+  },
+  NullableChanged {
+    owner: String,
+    property: String,
+    old: bool,
+    new: bool,
+  },
+  MaxLengthChanged {
+    owner: String,
+    property: String,
+    old: Option<u32>,
+    new: Option<u32>,
+  },
+  PrecisionChanged {
+    owner: String,
+    property: String,
+    old: Option<u32>,
+    new: Option<u32>,
+  },
+  ScaleChanged {
+    owner: String,
+    property: String,
+    old: Option<Scale>,
+    new: Option<Scale>,
+  },
+  NavigationAdded {
+    owner: String,
+    navigation: String,
+  },
+  NavigationRemoved {
+    owner: String,
+    navigation: String,
+  },
+  NavigationChanged {
+    owner: String,
+    navigation: String,
+    old: String,
+    new: String,
+  },
+}
+
+impl Change {
+  pub fn is_breaking(&self) -> bool {
+    match self {
+      Change::TypeAdded { .. } | Change::NavigationAdded { .. } => false,
+      Change::PropertyAdded { nullable, .. } => !nullable,
+      Change::NullableChanged { old, new, .. } => *old && !*new,
+      Change::MaxLengthChanged { old, new, .. } | Change::PrecisionChanged { old, new, .. } => {
+        is_narrowed(*old, *new)
+      }
+      Change::ScaleChanged { old, new, .. } => match (old, new) {
+        (_, None) | (_, Some(Scale::Variable)) | (_, Some(Scale::Floating)) => false,
+        (Some(Scale::Fixed(old)), Some(Scale::Fixed(new))) => new < old,
+        _ => true,
+      },
+      _ => true,
+    }
+  }
+
+  pub fn code(&self) -> &'static str {
+    match self {
+      Change::TypeAdded { .. } => "type-added",
+      Change::TypeRemoved { .. } => "type-removed",
+      Change::PropertyAdded { .. } => "property-added",
+      Change::PropertyRemoved { .. } => "property-removed",
+      Change::KeyChanged { .. } => "key-changed",
+      Change::PropertyTypeChanged { .. } => "property-type-changed",
+      Change::NullableChanged { .. } => "nullable-changed",
+      Change::MaxLengthChanged { .. } => "max-length-changed",
+      Change::PrecisionChanged { .. } => "precision-changed",
+      Change::ScaleChanged { .. } => "scale-changed",
+      Change::NavigationAdded { .. } => "navigation-added",
+      Change::NavigationRemoved { .. } => "navigation-removed",
+      Change::NavigationChanged { .. } => "navigation-changed",
+    }
+  }
+
+  pub fn owner(&self) -> &str {
+    match self {
+      Change::TypeAdded { owner }
+      | Change::TypeRemoved { owner }
+      | Change::PropertyAdded { owner, .. }
+      | Change::PropertyRemoved { owner, .. }
+      | Change::KeyChanged { owner, .. }
+      | Change::PropertyTypeChanged { owner, .. }
+      | Change::NullableChanged { owner, .. }
+      | Change::MaxLengthChanged { owner, .. }
+      | Change::PrecisionChanged { owner, .. }
+      | Change::ScaleChanged { owner, .. }
+      | Change::NavigationAdded { owner, .. }
+      | Change::NavigationRemoved { owner, .. }
+      | Change::NavigationChanged { owner, .. } => owner,
+    }
+  }
+
+  pub fn element(&self) -> Option<&str> {
+    match self {
+      Change::TypeAdded { .. } | Change::TypeRemoved { .. } | Change::KeyChanged { .. } => None,
+      Change::PropertyAdded { property, .. }
+      | Change::PropertyRemoved { property, .. }
+      | Change::PropertyTypeChanged { property, .. }
+      | Change::NullableChanged { property, .. }
+      | Change::MaxLengthChanged { property, .. }
+      | Change::PrecisionChanged { property, .. }
+      | Change::ScaleChanged { property, .. } => Some(property),
+      Change::NavigationAdded { navigation, .. }
+      | Change::NavigationRemoved { navigation, .. }
+      | Change::NavigationChanged { navigation, .. } => Some(navigation),
+    }
+  }
+
+  pub fn old_value(&self) -> Option<String> {
+    match self {
+      Change::KeyChanged { old, .. } => Some(old.join(", ")),
+      Change::PropertyTypeChanged { old, .. } | Change::NavigationChanged { old, .. } => {
+        Some(old.clone())
+      }
+      Change::NullableChanged { old, .. } => Some(old.to_string()),
+      Change::MaxLengthChanged { old, .. } | Change::PrecisionChanged { old, .. } => {
+        old.map(|old| old.to_string())
+      }
+      Change::ScaleChanged { old, .. } => old.map(|old| old.to_string()),
+      _ => None,
+    }
+  }
+
+  pub fn new_value(&self) -> Option<String> {
+    match self {
+      Change::KeyChanged { new, .. } => Some(new.join(", ")),
+      Change::PropertyTypeChanged { new, .. } | Change::NavigationChanged { new, .. } => {
+        Some(new.clone())
+      }
+      Change::NullableChanged { new, .. } => Some(new.to_string()),
+      Change::MaxLengthChanged { new, .. } | Change::PrecisionChanged { new, .. } => {
+        new.map(|new| new.to_string())
+      }
+      Change::ScaleChanged { new, .. } => new.map(|new| new.to_string()),
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for Change {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let value = |value: Option<String>| value.unwrap_or_else(|| "none".to_owned());
+    match self {
+      Change::TypeAdded { owner } => write!(formatter, "Added {}", owner),
+      Change::TypeRemoved { owner } => write!(formatter, "Removed {}", owner),
+      Change::PropertyAdded {
+        owner,
+        property,
+        nullable: true,
+      } => write!(formatter, "Added property {} to {}", property, owner),
+      Change::PropertyAdded {
+        owner, property, ..
+      } => write!(
+        formatter,
+        "Added non nullable property {} to {}",
+        property, owner
+      ),
+      Change::PropertyRemoved { owner, property } => {
+        write!(formatter, "Removed property {} of {}", property, owner)
+      }
+      Change::KeyChanged { owner, .. } => write!(
+        formatter,
+        "Changed key of {} from ({}) to ({})",
+        owner,
+        value(self.old_value()),
+        value(self.new_value())
+      ),
+      Change::NavigationAdded { owner, navigation } => {
+        write!(
+          formatter,
+          "Added navigation property {} to {}",
+          navigation, owner
+        )
+      }
+      Change::NavigationRemoved { owner, navigation } => write!(
+        formatter,
+        "Removed navigation property {} of {}",
+        navigation, owner
+      ),
+      Change::NavigationChanged {
+        owner,
+        navigation,
+        old,
+        new,
+      } => write!(
+        formatter,
+        "Changed target of navigation property {} of {} from {} to {}",
+        navigation, owner, old, new
+      ),
+      _ => {
+        let subject = match self {
+          Change::PropertyTypeChanged { .. } => "type",
+          Change::NullableChanged { .. } => "Nullable",
+          Change::MaxLengthChanged { .. } => "MaxLength",
+          Change::PrecisionChanged { .. } => "Precision",
+          _ => "Scale",
+        };
+        write!(
+          formatter,
+          "Changed {} of property {} of {} from {} to {}",
+          subject,
+          self.element().unwrap_or_default(),
+          self.owner(),
+          value(self.old_value()),
+          value(self.new_value())
+        )
+      }
+    }
+  }
+}
+
+pub fn diff(old: &Model, new: &Model) -> Vec<Change> {
+  let mut changes = Vec::new();
+  for (schema, old_type) in old.entity_types() {
+    let name = schema.qualify(old_type.name());
+    let owner = format!("entity type {}", name);
+    match new.find_entity_type(&name) {
+      Some((new_schema, new_type)) => diff_entity_type(
+        &owner,
+        (old, schema, old_type),
+        (new, new_schema, new_type),
+        &mut changes,
+      ),
+      None => changes.push(Change::TypeRemoved { owner }),
+    }
+  }
+  for (schema, new_type) in new.entity_types() {
+    let name = schema.qualify(new_type.name());
+    if old.find_entity_type(&name).is_none() {
+      changes.push(Change::TypeAdded {
+        owner: format!("entity type {}", name),
+      });
+    }
+  }
+  for (schema, old_type) in old.complex_types() {
+    let name = schema.qualify(old_type.name());
+    let owner = format!("complex type {}", name);
+    match new.find_complex_type(&name) {
+      Some((_, new_type)) => diff_properties(
+        &owner,
+        old_type.properties().iter().collect(),
+        new_type.properties().iter().collect(),
+        &mut changes,
+      ),
+      None => changes.push(Change::TypeRemoved { owner }),
+    }
+  }
+  for (schema, new_type) in new.complex_types() {
+    let name = schema.qualify(new_type.name());
+    if old.find_complex_type(&name).is_none() {
+      changes.push(Change::TypeAdded {
+        owner: format!("complex type {}", name),
+      });
+    }
+  }
+  changes
+}
+
+fn diff_entity_type(
+  owner: &str,
+  (old_model, old_schema, old_type): (&Model, &Schema, &EntityType),
+  (new_model, new_schema, new_type): (&Model, &Schema, &EntityType),
+  changes: &mut Vec<Change>,
+) {
+  if old_type.keys() != new_type.keys() {
+    changes.push(Change::KeyChanged {
+      owner: owner.to_owned(),
+      old: old_type.keys().to_vec(),
+      new: new_type.keys().to_vec(),
+    });
+  }
+  diff_properties(
+    owner,
+    old_type.properties().collect(),
+    new_type.properties().collect(),
+    changes,
+  );
+  for old_navigation in old_type.navigation_properties() {
+    let new_navigation = new_type
+      .navigation_properties()
+      .find(|navigation| navigation.name() == old_navigation.name());
+    match new_navigation {
+      Some(new_navigation) => {
+        let old_target = navigation_target(old_model, old_schema, old_navigation);
+        let new_target = navigation_target(new_model, new_schema, new_navigation);
+        if old_target != new_target {
+          changes.push(Change::NavigationChanged {
+            owner: owner.to_owned(),
+            navigation: old_navigation.name().to_owned(),
+            old: old_target,
+            new: new_target,
+          });
+        }
+      }
+      None => changes.push(Change::NavigationRemoved {
+        owner: owner.to_owned(),
+        navigation: old_navigation.name().to_owned(),
+      }),
+    }
+  }
+  for new_navigation in new_type.navigation_properties() {
+    if !old_type
+      .navigation_properties()
+      .any(|navigation| navigation.name() == new_navigation.name())
+    {
+      changes.push(Change::NavigationAdded {
+        owner: owner.to_owned(),
+        navigation: new_navigation.name().to_owned(),
+      });
+    }
+  }
+}
+
+fn diff_properties(
+  owner: &str,
+  old: Vec<&Property>,
+  new: Vec<&Property>,
+  changes: &mut Vec<Change>,
+) {
+  for old_property in old.iter() {
+    let property = old_property.name().to_owned();
+    let new_property = match new.iter().find(|new| new.name() == old_property.name()) {
+      Some(new_property) => new_property,
+      None => {
+        changes.push(Change::PropertyRemoved {
+          owner: owner.to_owned(),
+          property,
+        });
+        continue;
+      }
+    };
+    let old_type = property_type(old_property);
+    let new_type = property_type(new_property);
+    if old_type != new_type {
+      changes.push(Change::PropertyTypeChanged {
+        owner: owner.to_owned(),
+        property,
+        old: old_type,
+        new: new_type,
+      });
+      continue;
+    }
+    let (old_facets, new_facets) = (old_property.facets(), new_property.facets());
+    if old_facets.nullable() != new_facets.nullable() {
+      changes.push(Change::NullableChanged {
+        owner: owner.to_owned(),
+        property: property.clone(),
+        old: old_facets.nullable(),
+        new: new_facets.nullable(),
+      });
+    }
+    if old_facets.max_length() != new_facets.max_length() {
+      changes.push(Change::MaxLengthChanged {
+        owner: owner.to_owned(),
+        property: property.clone(),
+        old: old_facets.max_length(),
+        new: new_facets.max_length(),
+      });
+    }
+    if old_facets.precision() != new_facets.precision() {
+      changes.push(Change::PrecisionChanged {
+        owner: owner.to_owned(),
+        property: property.clone(),
+        old: old_facets.precision(),
+        new: new_facets.precision(),
+      });
+    }
+    if old_facets.scale() != new_facets.scale() {
+      changes.push(Change::ScaleChanged {
+        owner: owner.to_owned(),
+        property,
+        old: old_facets.scale(),
+        new: new_facets.scale(),
+      });
+    }
+  }
+  for new_property in new {
+    if !old.iter().any(|old| old.name() == new_property.name()) {
+      changes.push(Change::PropertyAdded {
+        owner: owner.to_owned(),
+        property: new_property.name().to_owned(),
+        nullable: new_property.facets().nullable(),
+      });
+    }
+  }
+}
+
+fn property_type(property: &Property) -> String {
+  let name = match (property.original_type(), property.property_type()) {
+    (Some(original_type), _) => original_type,
+    (None, PropertyType::Edm(edm_type)) => edm_type.name(),
+    (None, PropertyType::Complex(name)) | (None, PropertyType::Enum(name)) => name,
+  };
+  if property.is_collection() {
+    format!("Collection({})", name)
+  } else {
+    name.to_owned()
+  }
+}
+
+fn navigation_target(model: &Model, schema: &Schema, navigation: &NavigationProperty) -> String {
+  match model.resolve_navigation(schema, navigation) {
+    Some(target) => format!(
+      "{} [{}]",
+      target.schema().qualify(target.entity_type().name()),
+      target.multiplicity()
+    ),
+    None => navigation.target().to_owned(),
+  }
+}
+
+fn is_narrowed(old: Option<u32>, new: Option<u32>) -> bool {
+  match (old, new) {
+    (_, None) => false,
+    (None, Some(_)) => true,
+    (Some(old), Some(new)) => new < old,
+  }
+}
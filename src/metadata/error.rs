@@ -1,27 +1,222 @@
+use super::super::xml_tags::types::Position;
 use std::error;
 use std::fmt;
+use std::io;
 
-#[derive(fmt::Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(fmt::Debug)]
+pub enum ParserErrorKind {
+  MissingAttribute {
+    element: String,
+    attribute: String,
+  },
+  UnknownType {
+    name: String,
+  },
+  UnknownKeyProperty {
+    entity: String,
+    key: String,
+  },
+  UnknownAnnotationTarget {
+    target: String,
+  },
+  XmlSyntax {
+    message: String,
+    source: Option<Box<dyn error::Error + Send + Sync>>,
+  },
+  Io {
+    source: io::Error,
+  },
+}
+
+impl fmt::Display for ParserErrorKind {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParserErrorKind::MissingAttribute { element, attribute } => {
+        write!(
+          formatter,
+          "Missing attribute '{}' of {}",
+          attribute, element
+        )
+      }
+      ParserErrorKind::UnknownType { name } => {
+        write!(formatter, "Unknown/Unsupported OData Type '{}'", name)
+      }
+      ParserErrorKind::UnknownKeyProperty { entity, key } => write!(
+        formatter,
+        "Unknown key property '{}' of entity type {}",
+        key, entity
+      ),
+      ParserErrorKind::UnknownAnnotationTarget { target } => write!(
+        formatter,
+        "Unknown target '{}' of a value list annotation",
+        target
+      ),
+      ParserErrorKind::XmlSyntax { message, .. } => write!(formatter, "Invalid XML, {}", message),
+      ParserErrorKind::Io { source } => write!(formatter, "Failed to read metadata, {}", source),
+    }
+  }
+}
+
+#[derive(fmt::Debug)]
 pub struct ParserError {
-  message: String,
+  kind: ParserErrorKind,
+  position: Option<Position>,
+  context: Option<String>,
+  file: Option<String>,
+  snippet: Option<String>,
 }
 
 impl ParserError {
-  pub fn new_boxed<M: AsRef<str>>(message: M) -> Box<ParserError> {
-    Box::new(Self::new(message))
+  pub fn new_boxed(kind: ParserErrorKind) -> Box<ParserError> {
+    Box::new(Self::new(kind))
   }
 
-  pub fn new<M: AsRef<str>>(message: M) -> ParserError {
+  pub fn new(kind: ParserErrorKind) -> ParserError {
     ParserError {
-      message: message.as_ref().to_string(),
+      kind,
+      position: None,
+      context: None,
+      file: None,
+      snippet: None,
+    }
+  }
+
+  pub fn missing_attribute(element: &str, attribute: &str) -> Box<ParserError> {
+    Self::new_boxed(ParserErrorKind::MissingAttribute {
+      element: element.to_owned(),
+      attribute: attribute.to_owned(),
+    })
+  }
+
+  pub fn unknown_type(name: &str) -> Box<ParserError> {
+    Self::new_boxed(ParserErrorKind::UnknownType {
+      name: name.to_owned(),
+    })
+  }
+
+  pub(crate) fn downcast(
+    error: Box<dyn error::Error>,
+  ) -> Result<ParserError, Box<dyn error::Error>> {
+    match error.downcast::<ParserError>() {
+      Ok(error) => Ok(*error),
+      Err(error) => error.downcast::<Box<ParserError>>().map(|error| **error),
+    }
+  }
+
+  pub fn with_position(mut self, position: Option<Position>) -> ParserError {
+    if self.position.is_none() {
+      self.position = position;
+    }
+    self
+  }
+
+  pub fn with_context(mut self, context: Option<String>) -> ParserError {
+    if self.context.is_none() {
+      self.context = context;
+    }
+    self
+  }
+
+  pub fn with_file(mut self, file: Option<&str>) -> ParserError {
+    self.file = file.map(|file| file.to_owned());
+    self
+  }
+
+  pub fn with_source(self, file: Option<&str>, source: &str) -> ParserError {
+    let mut error = self.with_file(file);
+    if let Some(position) = error.position {
+      error.snippet = source
+        .lines()
+        .nth(position.line() as usize - 1)
+        .map(|line| line.to_owned());
     }
+    error
+  }
+
+  pub fn kind(&self) -> &ParserErrorKind {
+    &self.kind
+  }
+
+  pub fn line(&self) -> Option<u64> {
+    self.position.map(|position| position.line())
+  }
+
+  pub fn column(&self) -> Option<u64> {
+    self.position.map(|position| position.column())
+  }
+
+  pub fn context(&self) -> Option<&str> {
+    self.context.as_deref()
+  }
+
+  pub fn file(&self) -> Option<&str> {
+    self.file.as_deref()
+  }
+
+  fn fmt_location(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let line = self.position.map(|position| position.line().to_string());
+    let gutter = " ".repeat(line.as_ref().map(|line| line.len()).unwrap_or(1));
+    match (self.position, self.file.as_ref()) {
+      (Some(position), Some(file)) => write!(
+        formatter,
+        "\n{}--> {}:{}:{}",
+        gutter,
+        file,
+        position.line(),
+        position.column()
+      )?,
+      (Some(position), None) => write!(
+        formatter,
+        "\n{}--> {}:{}",
+        gutter,
+        position.line(),
+        position.column()
+      )?,
+      (None, Some(file)) => write!(formatter, "\n{}--> {}", gutter, file)?,
+      (None, None) => (),
+    }
+    if let (Some(position), Some(snippet)) = (self.position, self.snippet.as_ref()) {
+      let code = snippet.trim_start();
+      let indent = snippet.chars().count() - code.chars().count();
+      let offset = (position.column() as usize - 1).saturating_sub(indent);
+      write!(formatter, "\n{} |", gutter)?;
+      write!(
+        formatter,
+        "\n{} | {}",
+        line.unwrap_or_default(),
+        code.trim_end()
+      )?;
+      write!(formatter, "\n{} | {}^", gutter, " ".repeat(offset))?;
+    }
+    if let Some(context) = self.context.as_ref() {
+      write!(formatter, "\n{} = note: in {}", gutter, context)?;
+    }
+    Ok(())
   }
 }
 
 impl fmt::Display for ParserError {
   fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(formatter, "Metadata Parser Error, reason: {}", self.message)
+    write!(formatter, "Metadata Parser Error, reason: {}", self.kind)?;
+    self.fmt_location(formatter)
   }
 }
 
-impl error::Error for ParserError {}
+impl error::Error for ParserError {
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match &self.kind {
+      ParserErrorKind::XmlSyntax {
+        source: Some(source),
+        ..
+      } => Some(source.as_ref()),
+      ParserErrorKind::Io { source } => Some(source),
+      _ => None,
+    }
+  }
+}
+
+impl From<io::Error> for ParserError {
+  fn from(source: io::Error) -> ParserError {
+    ParserError::new(ParserErrorKind::Io { source })
+  }
+}
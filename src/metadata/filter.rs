@@ -0,0 +1,312 @@
+use super::model::{Element, EntityType, Model, Property, PropertyType, Schema};
+use std::collections::{HashSet, VecDeque};
+use std::error::Error;
+use std::io;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EntityFilter {
+  include: Vec<String>,
+  exclude: Vec<String>,
+  reachable_from: Vec<String>,
+  depth: Option<usize>,
+}
+
+#[derive(Default)]
+struct SchemaPlan {
+  entity_types: Vec<bool>,
+  navigations: Vec<Vec<String>>,
+  complex_types: Vec<bool>,
+  enum_types: Vec<bool>,
+  associations: Vec<bool>,
+  entity_sets: Vec<bool>,
+  operations: Vec<bool>,
+  operation_imports: Vec<bool>,
+  operation_types: Vec<String>,
+}
+
+impl EntityFilter {
+  pub fn with_include(mut self, pattern: &str) -> Self {
+    self.include.push(pattern.to_owned());
+    self
+  }
+
+  pub fn with_exclude(mut self, pattern: &str) -> Self {
+    self.exclude.push(pattern.to_owned());
+    self
+  }
+
+  pub fn with_reachable_from(mut self, entity_type: &str) -> Self {
+    self.reachable_from.push(entity_type.to_owned());
+    self
+  }
+
+  pub fn with_depth(mut self, depth: Option<usize>) -> Self {
+    self.depth = depth;
+    self
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.include.is_empty() && self.exclude.is_empty() && self.reachable_from.is_empty()
+  }
+
+  pub fn apply(&self, model: &mut Model) -> Result<(), Box<dyn Error>> {
+    if self.is_empty() {
+      return Ok(());
+    }
+    let kept = self.kept_entity_types(model)?;
+    let mut plans: Vec<_> = model
+      .schemas()
+      .iter()
+      .map(|schema| Self::plan(model, schema, &kept))
+      .collect();
+    let operation_types = plans
+      .iter_mut()
+      .flat_map(|plan| plan.operation_types.drain(..))
+      .collect();
+    let used = Self::used_types(model, &kept, operation_types);
+    for (schema, plan) in model.schemas().iter().zip(plans.iter_mut()) {
+      for complex_type in schema.complex_types() {
+        plan
+          .complex_types
+          .push(used.contains(&schema.qualify(complex_type.name())));
+      }
+      for enum_type in schema.enum_types() {
+        plan
+          .enum_types
+          .push(used.contains(&schema.qualify(enum_type.name())));
+      }
+    }
+    for (schema, plan) in model.schemas_mut().iter_mut().zip(plans) {
+      let mut navigations = plan.navigations.into_iter();
+      for entity_type in schema.entity_types_mut() {
+        let dropped = navigations.next().unwrap_or_default();
+        entity_type.elements_mut().retain(|element| match element {
+          Element::NavigationProperty(navigation) => {
+            !dropped.iter().any(|name| name == navigation.name())
+          }
+          Element::Property(_) => true,
+        });
+      }
+      retain(schema.entity_types_mut(), &plan.entity_types);
+      retain(schema.complex_types_mut(), &plan.complex_types);
+      retain(schema.enum_types_mut(), &plan.enum_types);
+      retain(schema.associations_mut(), &plan.associations);
+      retain(schema.entity_sets_mut(), &plan.entity_sets);
+      retain(schema.operations_mut(), &plan.operations);
+      retain(schema.operation_imports_mut(), &plan.operation_imports);
+    }
+    Ok(())
+  }
+
+  fn is_selected(&self, schema: &Schema, entity_type: &EntityType) -> bool {
+    let qualified_name = schema.qualify(entity_type.name());
+    let matches = |pattern: &String| {
+      glob_match(pattern, entity_type.name()) || glob_match(pattern, &qualified_name)
+    };
+    (self.include.is_empty() || self.include.iter().any(matches))
+      && !self.exclude.iter().any(matches)
+  }
+
+  fn kept_entity_types(&self, model: &Model) -> Result<HashSet<String>, Box<dyn Error>> {
+    let mut kept: HashSet<String> = model
+      .entity_types()
+      .filter(|(schema, entity_type)| self.is_selected(schema, entity_type))
+      .map(|(schema, entity_type)| schema.qualify(entity_type.name()))
+      .collect();
+    if !self.reachable_from.is_empty() {
+      let mut reachable = HashSet::new();
+      let mut queue = VecDeque::new();
+      for root in self.reachable_from.iter() {
+        let (schema, entity_type) = model
+          .find_entity_type(root)
+          .or_else(|| {
+            model
+              .entity_types()
+              .find(|(_, entity_type)| entity_type.name() == root)
+          })
+          .ok_or_else(|| {
+            io::Error::new(
+              io::ErrorKind::InvalidInput,
+              format!("Unknown entity type '{}' passed to --reachable-from", root),
+            )
+          })?;
+        queue.push_back((schema, entity_type, 0));
+      }
+      while let Some((schema, entity_type, depth)) = queue.pop_front() {
+        if !reachable.insert(schema.qualify(entity_type.name())) {
+          continue;
+        }
+        if self.depth.is_some_and(|max_depth| depth >= max_depth) {
+          continue;
+        }
+        for navigation in entity_type.navigation_properties() {
+          if let Some(target) = model.resolve_navigation(schema, navigation) {
+            if kept.contains(&target.schema().qualify(target.entity_type().name())) {
+              queue.push_back((target.schema(), target.entity_type(), depth + 1));
+            }
+          }
+        }
+      }
+      kept.retain(|name| reachable.contains(name));
+    }
+    let base_types: Vec<_> = model
+      .entity_types()
+      .filter(|(schema, entity_type)| kept.contains(&schema.qualify(entity_type.name())))
+      .flat_map(|(schema, entity_type)| model.entity_type_hierarchy(schema, entity_type))
+      .map(|(schema, entity_type)| schema.qualify(entity_type.name()))
+      .collect();
+    kept.extend(base_types);
+    Ok(kept)
+  }
+
+  fn plan(model: &Model, schema: &Schema, kept: &HashSet<String>) -> SchemaPlan {
+    let is_kept = |found: Option<(&Schema, &EntityType)>| {
+      found.is_none_or(|(schema, entity_type)| kept.contains(&schema.qualify(entity_type.name())))
+    };
+    let find_entity_type = |name: &str| {
+      model.find_entity_type(name).or_else(|| {
+        let name = name.rsplit('.').next()?;
+        schema
+          .entity_type(name)
+          .map(|entity_type| (schema, entity_type))
+      })
+    };
+    let mut plan = SchemaPlan::default();
+    for entity_type in schema.entity_types() {
+      plan
+        .entity_types
+        .push(kept.contains(&schema.qualify(entity_type.name())));
+      let dangling = entity_type
+        .navigation_properties()
+        .filter(|navigation| {
+          let target = model.resolve_navigation(schema, navigation);
+          !is_kept(target.map(|target| (target.schema(), target.entity_type())))
+        })
+        .map(|navigation| navigation.name().to_owned())
+        .collect();
+      plan.navigations.push(dangling);
+    }
+    for association in schema.associations() {
+      plan.associations.push(
+        association
+          .ends()
+          .iter()
+          .all(|end| is_kept(find_entity_type(end.entity_type()))),
+      );
+    }
+    for entity_set in schema.entity_sets() {
+      plan
+        .entity_sets
+        .push(is_kept(find_entity_type(entity_set.entity_type())));
+    }
+    let mut used_types = Vec::new();
+    for operation in schema.operations() {
+      let type_names: Vec<_> = operation
+        .parameters()
+        .iter()
+        .map(|parameter| parameter.type_ref().name())
+        .chain(
+          operation
+            .return_type()
+            .map(|return_type| return_type.name()),
+        )
+        .collect();
+      let keep = type_names
+        .iter()
+        .all(|name| is_kept(model.find_entity_type(name)));
+      if keep {
+        used_types.extend(type_names.iter().map(|name| name.to_string()));
+      }
+      plan.operations.push(keep);
+    }
+    for operation_import in schema.operation_imports() {
+      let operation = operation_import.operation();
+      let operation_kept = schema
+        .operations()
+        .iter()
+        .zip(plan.operations.iter())
+        .filter(|(candidate, _)| {
+          schema.qualify(candidate.name()) == operation || candidate.name() == operation
+        })
+        .all(|(_, keep)| *keep);
+      let entity_set_kept = operation_import.entity_set().is_none_or(|name| {
+        schema
+          .entity_sets()
+          .iter()
+          .zip(plan.entity_sets.iter())
+          .all(|(entity_set, keep)| entity_set.name() != name || *keep)
+      });
+      plan
+        .operation_imports
+        .push(operation_kept && entity_set_kept);
+    }
+    plan.operation_types = used_types;
+    plan
+  }
+
+  fn used_types(
+    model: &Model,
+    kept: &HashSet<String>,
+    mut pending: Vec<String>,
+  ) -> HashSet<String> {
+    for (schema, entity_type) in model.entity_types() {
+      if kept.contains(&schema.qualify(entity_type.name())) {
+        pending.extend(entity_type.properties().filter_map(named_type));
+      }
+    }
+    let mut used = HashSet::new();
+    while let Some(name) = pending.pop() {
+      if let Some((schema, complex_type)) = model.find_complex_type(&name) {
+        if used.insert(schema.qualify(complex_type.name())) {
+          pending.extend(complex_type.properties().iter().filter_map(named_type));
+          pending.extend(complex_type.base_type().map(str::to_owned));
+        }
+      } else if let Some((schema, enum_type)) = model.find_enum_type(&name) {
+        used.insert(schema.qualify(enum_type.name()));
+      }
+    }
+    used
+  }
+}
+
+fn named_type(property: &Property) -> Option<String> {
+  match property.property_type() {
+    PropertyType::Complex(name) | PropertyType::Enum(name) => Some(name.clone()),
+    PropertyType::Edm(_) => None,
+  }
+}
+
+fn retain<T>(items: &mut Vec<T>, keep: &[bool]) {
+  let mut keep = keep.iter();
+  items.retain(|_| *keep.next().unwrap_or(&true));
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+  let pattern: Vec<char> = pattern.chars().collect();
+  let text: Vec<char> = text.chars().collect();
+  let (mut pattern_index, mut text_index) = (0, 0);
+  let mut backtrack = None;
+  while text_index < text.len() {
+    match pattern.get(pattern_index) {
+      Some('*') => {
+        backtrack = Some((pattern_index, text_index));
+        pattern_index += 1;
+      }
+      Some(current) if *current == '?' || *current == text[text_index] => {
+        pattern_index += 1;
+        text_index += 1;
+      }
+      _ => match backtrack {
+        Some((star_index, star_text_index)) => {
+          pattern_index = star_index + 1;
+          text_index = star_text_index + 1;
+          backtrack = Some((star_index, star_text_index + 1));
+        }
+        None => return false,
+      },
+    }
+  }
+  pattern[pattern_index..]
+    .iter()
+    .all(|current| *current == '*')
+}
@@ -1,9 +1,15 @@
-mod cds;
+mod diff;
 mod error;
+mod filter;
+pub mod model;
 mod parser;
+mod validation;
 
 #[cfg(test)]
 mod tests;
 
-pub use error::ParserError;
+pub use diff::{diff, Change};
+pub use error::{ParserError, ParserErrorKind};
+pub use filter::EntityFilter;
 pub use parser::Parser;
+pub use validation::{validate, Severity, ValidationError, ValidationIssue};
@@ -0,0 +1,100 @@
+use super::referential_constraint::AssociationConstraint;
+use std::fmt::{Display, Formatter, Result as FMTResult};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Multiplicity {
+  ZeroOrOne,
+  One,
+  Many,
+}
+
+impl Multiplicity {
+  pub fn from_odata(value: &str) -> Option<Self> {
+    match value {
+      "0..1" => Some(Self::ZeroOrOne),
+      "1" => Some(Self::One),
+      "*" => Some(Self::Many),
+      _ => None,
+    }
+  }
+}
+
+impl Display for Multiplicity {
+  fn fmt(&self, fmt: &mut Formatter) -> FMTResult {
+    match self {
+      Self::ZeroOrOne => write!(fmt, "0..1"),
+      Self::One => write!(fmt, "1"),
+      Self::Many => write!(fmt, "*"),
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct AssociationEnd {
+  role: String,
+  entity_type: String,
+  multiplicity: Multiplicity,
+}
+
+impl AssociationEnd {
+  pub fn new(role: &str, entity_type: &str, multiplicity: Multiplicity) -> Self {
+    AssociationEnd {
+      role: role.to_owned(),
+      entity_type: entity_type.to_owned(),
+      multiplicity,
+    }
+  }
+
+  pub fn role(&self) -> &str {
+    &self.role
+  }
+
+  pub fn entity_type(&self) -> &str {
+    &self.entity_type
+  }
+
+  pub fn multiplicity(&self) -> Multiplicity {
+    self.multiplicity
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct Association {
+  name: String,
+  ends: Vec<AssociationEnd>,
+  constraint: Option<AssociationConstraint>,
+}
+
+impl Association {
+  pub fn new(name: &str) -> Self {
+    Association {
+      name: name.to_owned(),
+      ends: Vec::new(),
+      constraint: None,
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn ends(&self) -> &[AssociationEnd] {
+    &self.ends
+  }
+
+  pub fn end(&self, role: &str) -> Option<&AssociationEnd> {
+    self.ends.iter().find(|end| end.role() == role)
+  }
+
+  pub fn add_end(&mut self, end: AssociationEnd) {
+    self.ends.push(end);
+  }
+
+  pub fn constraint(&self) -> Option<&AssociationConstraint> {
+    self.constraint.as_ref()
+  }
+
+  pub fn set_constraint(&mut self, constraint: AssociationConstraint) {
+    self.constraint = Some(constraint);
+  }
+}
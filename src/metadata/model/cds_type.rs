@@ -0,0 +1,164 @@
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CdsType {
+  name: String,
+  arguments: Vec<String>,
+  annotations: Vec<CdsAnnotation>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CdsAnnotation {
+  term: String,
+  value: CdsValue,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CdsValue {
+  String(String),
+  Number(String),
+  Boolean(bool),
+  Symbol(String),
+}
+
+impl CdsType {
+  pub fn parse(value: &str) -> Option<Self> {
+    let (type_name, annotations) = match value.find('@') {
+      Some(index) => value.split_at(index),
+      None => (value, ""),
+    };
+    let type_name = type_name.trim();
+    let (name, arguments) = match type_name.split_once('(') {
+      Some((name, arguments)) => {
+        let arguments = arguments.strip_suffix(')')?;
+        let arguments: Vec<_> = arguments
+          .split(',')
+          .map(|argument| argument.trim().to_owned())
+          .collect();
+        if arguments.iter().any(String::is_empty) {
+          return None;
+        }
+        (name.trim(), arguments)
+      }
+      None => (type_name, Vec::new()),
+    };
+    if !is_name(name) {
+      return None;
+    }
+    Some(CdsType {
+      name: name.to_owned(),
+      arguments,
+      annotations: parse_annotations(annotations)?,
+    })
+  }
+
+  pub fn new(name: &str) -> Self {
+    CdsType {
+      name: name.to_owned(),
+      arguments: Vec::new(),
+      annotations: Vec::new(),
+    }
+  }
+
+  pub fn with_annotation(mut self, term: &str, value: CdsValue) -> Self {
+    self.annotations.push(CdsAnnotation {
+      term: term.to_owned(),
+      value,
+    });
+    self
+  }
+
+  pub fn with_arguments(mut self, arguments: Vec<String>) -> Self {
+    self.arguments = arguments;
+    self
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn arguments(&self) -> &[String] {
+    &self.arguments
+  }
+
+  pub fn annotations(&self) -> &[CdsAnnotation] {
+    &self.annotations
+  }
+}
+
+impl CdsAnnotation {
+  pub fn term(&self) -> &str {
+    &self.term
+  }
+
+  pub fn value(&self) -> &CdsValue {
+    &self.value
+  }
+}
+
+fn parse_annotations(source: &str) -> Option<Vec<CdsAnnotation>> {
+  let mut annotations = Vec::new();
+  let mut rest = source.trim_start();
+  while let Some(annotation) = rest.strip_prefix('@') {
+    let end = annotation
+      .find(|c: char| c == ':' || c == '@' || c.is_whitespace())
+      .unwrap_or(annotation.len());
+    let (term, after_term) = annotation.split_at(end);
+    if !is_name(term) {
+      return None;
+    }
+    let after_term = after_term.trim_start();
+    let (value, after_value) = match after_term.strip_prefix(':') {
+      Some(value) => parse_value(value.trim_start())?,
+      None => (CdsValue::Boolean(true), after_term),
+    };
+    annotations.push(CdsAnnotation {
+      term: term.to_owned(),
+      value,
+    });
+    rest = after_value.trim_start();
+  }
+  if rest.is_empty() {
+    Some(annotations)
+  } else {
+    None
+  }
+}
+
+fn parse_value(source: &str) -> Option<(CdsValue, &str)> {
+  if let Some(quoted) = source.strip_prefix('\'') {
+    let mut value = String::new();
+    let mut chars = quoted.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+      if c != '\'' {
+        value.push(c);
+      } else if chars.peek().is_some_and(|(_, next)| *next == '\'') {
+        chars.next();
+        value.push('\'');
+      } else {
+        return Some((CdsValue::String(value), &quoted[index + 1..]));
+      }
+    }
+    return None;
+  }
+  let end = source
+    .find(|c: char| c == '@' || c.is_whitespace())
+    .unwrap_or(source.len());
+  let (token, rest) = source.split_at(end);
+  let value = match token {
+    "true" => CdsValue::Boolean(true),
+    "false" => CdsValue::Boolean(false),
+    _ => match token.strip_prefix('#') {
+      Some(symbol) if is_name(symbol) => CdsValue::Symbol(symbol.to_owned()),
+      Some(_) => return None,
+      None if token.parse::<f64>().is_ok() => CdsValue::Number(token.to_owned()),
+      None => return None,
+    },
+  };
+  Some((value, rest))
+}
+
+fn is_name(name: &str) -> bool {
+  !name.is_empty()
+    && name
+      .split('.')
+      .all(|part| !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_'))
+}
@@ -0,0 +1,42 @@
+use super::property::Property;
+
+#[derive(Clone, Debug)]
+pub struct ComplexType {
+  name: String,
+  base_type: Option<String>,
+  properties: Vec<Property>,
+}
+
+impl ComplexType {
+  pub fn new(name: &str) -> Self {
+    ComplexType {
+      name: name.to_owned(),
+      base_type: None,
+      properties: Vec::new(),
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn base_type(&self) -> Option<&str> {
+    self.base_type.as_deref()
+  }
+
+  pub fn set_base_type(&mut self, base_type: &str) {
+    self.base_type = Some(base_type.to_owned());
+  }
+
+  pub fn properties(&self) -> &[Property] {
+    &self.properties
+  }
+
+  pub fn properties_mut(&mut self) -> &mut Vec<Property> {
+    &mut self.properties
+  }
+
+  pub fn add_property(&mut self, property: Property) {
+    self.properties.push(property);
+  }
+}
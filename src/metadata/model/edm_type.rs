@@ -0,0 +1,78 @@
+use std::fmt::{Display, Formatter, Result as FMTResult};
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum EdmType {
+  Guid,
+  Boolean,
+  Int16,
+  Int32,
+  Int64,
+  Decimal,
+  Double,
+  Single,
+  Byte,
+  SByte,
+  Date,
+  TimeOfDay,
+  Time,
+  DateTime,
+  DateTimeOffset,
+  String,
+  Binary,
+  Stream,
+}
+
+impl EdmType {
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "Edm.Guid" => Some(Self::Guid),
+      "Edm.Boolean" => Some(Self::Boolean),
+      "Edm.Int16" => Some(Self::Int16),
+      "Edm.Int32" => Some(Self::Int32),
+      "Edm.Int64" => Some(Self::Int64),
+      "Edm.Decimal" => Some(Self::Decimal),
+      "Edm.Double" => Some(Self::Double),
+      "Edm.Single" => Some(Self::Single),
+      "Edm.Byte" => Some(Self::Byte),
+      "Edm.SByte" => Some(Self::SByte),
+      "Edm.Date" => Some(Self::Date),
+      "Edm.TimeOfDay" => Some(Self::TimeOfDay),
+      "Edm.Time" => Some(Self::Time),
+      "Edm.DateTime" => Some(Self::DateTime),
+      "Edm.DateTimeOffset" => Some(Self::DateTimeOffset),
+      "Edm.String" => Some(Self::String),
+      "Edm.Binary" => Some(Self::Binary),
+      "Edm.Stream" => Some(Self::Stream),
+      _ => None,
+    }
+  }
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::Guid => "Edm.Guid",
+      Self::Boolean => "Edm.Boolean",
+      Self::Int16 => "Edm.Int16",
+      Self::Int32 => "Edm.Int32",
+      Self::Int64 => "Edm.Int64",
+      Self::Decimal => "Edm.Decimal",
+      Self::Double => "Edm.Double",
+      Self::Single => "Edm.Single",
+      Self::Byte => "Edm.Byte",
+      Self::SByte => "Edm.SByte",
+      Self::Date => "Edm.Date",
+      Self::TimeOfDay => "Edm.TimeOfDay",
+      Self::Time => "Edm.Time",
+      Self::DateTime => "Edm.DateTime",
+      Self::DateTimeOffset => "Edm.DateTimeOffset",
+      Self::String => "Edm.String",
+      Self::Binary => "Edm.Binary",
+      Self::Stream => "Edm.Stream",
+    }
+  }
+}
+
+impl Display for EdmType {
+  fn fmt(&self, fmt: &mut Formatter) -> FMTResult {
+    write!(fmt, "{}", self.name())
+  }
+}
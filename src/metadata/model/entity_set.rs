@@ -0,0 +1,22 @@
+#[derive(Clone, Debug)]
+pub struct EntitySet {
+  name: String,
+  entity_type: String,
+}
+
+impl EntitySet {
+  pub fn new(name: &str, entity_type: &str) -> Self {
+    EntitySet {
+      name: name.to_owned(),
+      entity_type: entity_type.to_owned(),
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn entity_type(&self) -> &str {
+    &self.entity_type
+  }
+}
@@ -0,0 +1,118 @@
+use super::navigation_property::NavigationProperty;
+use super::property::Property;
+
+#[derive(Clone, Debug)]
+pub enum Element {
+  Property(Property),
+  NavigationProperty(NavigationProperty),
+}
+
+impl Element {
+  pub fn name(&self) -> &str {
+    match self {
+      Element::Property(property) => property.name(),
+      Element::NavigationProperty(navigation) => navigation.name(),
+    }
+  }
+
+  pub fn cds_name(&self) -> Option<&str> {
+    match self {
+      Element::Property(property) => property.cds_name(),
+      Element::NavigationProperty(navigation) => navigation.cds_name(),
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct EntityType {
+  name: String,
+  base_type: Option<String>,
+  keys: Vec<String>,
+  elements: Vec<Element>,
+  cds_name: Option<String>,
+}
+
+impl EntityType {
+  pub fn new(name: &str) -> Self {
+    EntityType {
+      name: name.to_owned(),
+      base_type: None,
+      keys: Vec::new(),
+      elements: Vec::new(),
+      cds_name: None,
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn cds_name(&self) -> Option<&str> {
+    self.cds_name.as_deref()
+  }
+
+  pub fn set_cds_name(&mut self, cds_name: &str) {
+    self.cds_name = Some(cds_name.to_owned());
+  }
+
+  pub fn base_type(&self) -> Option<&str> {
+    self.base_type.as_deref()
+  }
+
+  pub fn set_base_type(&mut self, base_type: &str) {
+    self.base_type = Some(base_type.to_owned());
+  }
+
+  pub fn keys(&self) -> &[String] {
+    &self.keys
+  }
+
+  pub fn is_key(&self, name: &str) -> bool {
+    self.keys.iter().any(|key| key == name)
+  }
+
+  pub fn add_key(&mut self, name: &str) {
+    self.keys.push(name.to_owned());
+  }
+
+  pub fn remove_key(&mut self, name: &str) {
+    self.keys.retain(|key| key != name);
+  }
+
+  pub fn elements(&self) -> &[Element] {
+    &self.elements
+  }
+
+  pub fn elements_mut(&mut self) -> &mut Vec<Element> {
+    &mut self.elements
+  }
+
+  pub fn add_element(&mut self, element: Element) {
+    self.elements.push(element);
+  }
+
+  pub fn properties(&self) -> impl Iterator<Item = &Property> {
+    self.elements.iter().filter_map(|element| match element {
+      Element::Property(property) => Some(property),
+      _ => None,
+    })
+  }
+
+  pub fn property(&self, name: &str) -> Option<&Property> {
+    self.properties().find(|property| property.name() == name)
+  }
+
+  pub fn property_mut(&mut self, name: &str) -> Option<&mut Property> {
+    self.elements.iter_mut().find_map(|element| match element {
+      Element::Property(property) if property.name() == name => Some(property),
+      _ => None,
+    })
+  }
+
+  pub fn navigation_properties(&self) -> impl Iterator<Item = &NavigationProperty> {
+    self.elements.iter().filter_map(|element| match element {
+      Element::NavigationProperty(navigation) => Some(navigation),
+      _ => None,
+    })
+  }
+}
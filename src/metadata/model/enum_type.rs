@@ -0,0 +1,66 @@
+use super::edm_type::EdmType;
+
+#[derive(Clone, Debug)]
+pub struct EnumType {
+  name: String,
+  underlying_type: EdmType,
+  is_flags: bool,
+  members: Vec<EnumMember>,
+}
+
+impl EnumType {
+  pub fn new(name: &str, underlying_type: EdmType, is_flags: bool) -> Self {
+    EnumType {
+      name: name.to_owned(),
+      underlying_type,
+      is_flags,
+      members: Vec::new(),
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn underlying_type(&self) -> EdmType {
+    self.underlying_type
+  }
+
+  pub fn is_flags(&self) -> bool {
+    self.is_flags
+  }
+
+  pub fn members(&self) -> &[EnumMember] {
+    &self.members
+  }
+
+  pub fn add_member(&mut self, name: &str, value: Option<i64>) {
+    let value = value.unwrap_or_else(|| {
+      self
+        .members
+        .last()
+        .map(|member| member.value + 1)
+        .unwrap_or(0)
+    });
+    self.members.push(EnumMember {
+      name: name.to_owned(),
+      value,
+    });
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumMember {
+  name: String,
+  value: i64,
+}
+
+impl EnumMember {
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn value(&self) -> i64 {
+    self.value
+  }
+}
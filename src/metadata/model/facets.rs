@@ -0,0 +1,79 @@
+use std::fmt::{Display, Formatter, Result as FMTResult};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Facets {
+  nullable: bool,
+  max_length: Option<u32>,
+  precision: Option<u32>,
+  scale: Option<Scale>,
+}
+
+impl Default for Facets {
+  fn default() -> Self {
+    Facets {
+      nullable: true,
+      max_length: None,
+      precision: None,
+      scale: None,
+    }
+  }
+}
+
+impl Facets {
+  pub fn new(
+    nullable: bool,
+    max_length: Option<u32>,
+    precision: Option<u32>,
+    scale: Option<Scale>,
+  ) -> Self {
+    Facets {
+      nullable,
+      max_length,
+      precision,
+      scale,
+    }
+  }
+
+  pub fn nullable(&self) -> bool {
+    self.nullable
+  }
+
+  pub fn max_length(&self) -> Option<u32> {
+    self.max_length
+  }
+
+  pub fn precision(&self) -> Option<u32> {
+    self.precision
+  }
+
+  pub fn scale(&self) -> Option<Scale> {
+    self.scale
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scale {
+  Fixed(u32),
+  Variable,
+  Floating,
+}
+
+impl Scale {
+  pub fn from_odata(value: &str) -> Option<Self> {
+    match value {
+      "variable" => Some(Self::Variable),
+      "floating" => Some(Self::Floating),
+      _ => value.parse().ok().map(Self::Fixed),
+    }
+  }
+}
+
+impl Display for Scale {
+  fn fmt(&self, fmt: &mut Formatter) -> FMTResult {
+    match self {
+      Self::Fixed(scale) => write!(fmt, "{}", scale),
+      Self::Variable => write!(fmt, "variable"),
+      Self::Floating => write!(fmt, "floating"),
+    }
+  }
+}
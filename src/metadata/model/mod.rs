@@ -0,0 +1,314 @@
+mod association;
+mod cds_type;
+mod complex_type;
+mod edm_type;
+mod entity_set;
+mod entity_type;
+mod enum_type;
+mod facets;
+mod navigation_property;
+mod operation;
+mod property;
+mod referential_constraint;
+mod schema;
+mod value_list;
+mod version;
+
+pub use association::{Association, AssociationEnd, Multiplicity};
+pub use cds_type::{CdsAnnotation, CdsType, CdsValue};
+pub use complex_type::ComplexType;
+pub use edm_type::EdmType;
+pub use entity_set::EntitySet;
+pub use entity_type::{Element, EntityType};
+pub use enum_type::{EnumMember, EnumType};
+pub use facets::{Facets, Scale};
+pub use navigation_property::{NavigationProperty, NavigationTarget};
+pub use operation::{Operation, OperationImport, OperationKind, Parameter, TypeRef};
+pub use property::{Property, PropertyType};
+pub use referential_constraint::{AssociationConstraint, ForeignKey, ReferentialConstraint};
+pub use schema::Schema;
+pub use value_list::{ValueList, ValueListParameter, ValueListParameterKind};
+pub use version::ODataVersion;
+
+#[derive(Clone, Debug, Default)]
+pub struct Model {
+  version: Option<ODataVersion>,
+  schemas: Vec<Schema>,
+}
+
+impl Model {
+  pub fn new(schemas: Vec<Schema>) -> Self {
+    Model {
+      version: None,
+      schemas,
+    }
+  }
+
+  pub fn version(&self) -> Option<ODataVersion> {
+    self.version
+  }
+
+  pub fn set_version(&mut self, version: ODataVersion) {
+    self.version = Some(version);
+  }
+
+  pub fn schemas(&self) -> &[Schema] {
+    &self.schemas
+  }
+
+  pub fn schemas_mut(&mut self) -> &mut Vec<Schema> {
+    &mut self.schemas
+  }
+
+  pub fn merge(&mut self, other: Model) {
+    if self.version.is_none() {
+      self.version = other.version;
+    }
+    for schema in other.schemas {
+      match self
+        .schemas
+        .iter_mut()
+        .find(|existing| existing.namespace() == schema.namespace())
+      {
+        Some(existing) => existing.merge(schema),
+        None => self.schemas.push(schema),
+      }
+    }
+  }
+
+  pub fn entity_types(&self) -> impl Iterator<Item = (&Schema, &EntityType)> {
+    self.schemas.iter().flat_map(|schema| {
+      schema
+        .entity_types()
+        .iter()
+        .map(move |entity_type| (schema, entity_type))
+    })
+  }
+
+  pub fn find_entity_type(&self, qualified_name: &str) -> Option<(&Schema, &EntityType)> {
+    self.schemas.iter().find_map(|schema| {
+      let name = schema.unqualify(qualified_name)?;
+      schema
+        .entity_type(name)
+        .map(|entity_type| (schema, entity_type))
+    })
+  }
+
+  pub fn resolve_entity_type(&self, name: &str) -> Option<(&Schema, &EntityType)> {
+    self.find_entity_type(name).or_else(|| {
+      let short_name = name.rsplit('.').next().unwrap_or(name);
+      self
+        .entity_types()
+        .find(|(_, entity_type)| entity_type.name() == short_name)
+    })
+  }
+
+  pub fn complex_types(&self) -> impl Iterator<Item = (&Schema, &ComplexType)> {
+    self.schemas.iter().flat_map(|schema| {
+      schema
+        .complex_types()
+        .iter()
+        .map(move |complex_type| (schema, complex_type))
+    })
+  }
+
+  pub fn find_complex_type(&self, qualified_name: &str) -> Option<(&Schema, &ComplexType)> {
+    self.schemas.iter().find_map(|schema| {
+      let name = schema.unqualify(qualified_name)?;
+      schema
+        .complex_type(name)
+        .map(|complex_type| (schema, complex_type))
+    })
+  }
+
+  pub fn enum_types(&self) -> impl Iterator<Item = (&Schema, &EnumType)> {
+    self.schemas.iter().flat_map(|schema| {
+      schema
+        .enum_types()
+        .iter()
+        .map(move |enum_type| (schema, enum_type))
+    })
+  }
+
+  pub fn find_enum_type(&self, qualified_name: &str) -> Option<(&Schema, &EnumType)> {
+    self.schemas.iter().find_map(|schema| {
+      let name = schema.unqualify(qualified_name)?;
+      schema.enum_type(name).map(|enum_type| (schema, enum_type))
+    })
+  }
+
+  pub fn find_operation(&self, qualified_name: &str) -> Option<(&Schema, &Operation)> {
+    self.schemas.iter().find_map(|schema| {
+      let name = schema.unqualify(qualified_name)?;
+      schema
+        .operations()
+        .iter()
+        .find(|operation| operation.name() == name)
+        .map(|operation| (schema, operation))
+    })
+  }
+
+  pub fn resolve_navigation<'a>(
+    &'a self,
+    schema: &'a Schema,
+    navigation: &NavigationProperty,
+  ) -> Option<NavigationTarget<'a>> {
+    if let (Some(relationship), Some(to_role)) = (navigation.relationship(), navigation.to_role()) {
+      let (association_schema, association) = self.find_association(relationship)?;
+      let end = association.end(to_role)?;
+      let (schema, entity_type) = self.find_entity_type(end.entity_type()).or_else(|| {
+        let name = end.entity_type().rsplit('.').next()?;
+        association_schema
+          .entity_type(name)
+          .map(|entity_type| (association_schema, entity_type))
+      })?;
+      let source_multiplicity = navigation
+        .from_role()
+        .and_then(|from_role| association.end(from_role))
+        .map(|end| end.multiplicity());
+      return Some(
+        NavigationTarget::new(schema, entity_type, end.multiplicity())
+          .with_source_multiplicity(source_multiplicity),
+      );
+    }
+    let (schema, entity_type) = schema
+      .entity_type(navigation.target())
+      .map(|entity_type| (schema, entity_type))
+      .or_else(|| self.resolve_entity_type(navigation.target()))?;
+    let source_multiplicity = navigation.partner().and_then(|partner| {
+      entity_type
+        .navigation_properties()
+        .find(|navigation| navigation.name() == partner)
+        .map(|partner| partner.multiplicity())
+    });
+    Some(
+      NavigationTarget::new(schema, entity_type, navigation.multiplicity())
+        .with_source_multiplicity(source_multiplicity),
+    )
+  }
+
+  pub fn navigation_constraints(
+    &self,
+    navigation: &NavigationProperty,
+  ) -> Vec<ReferentialConstraint> {
+    if !navigation.referential_constraints().is_empty() {
+      return navigation.referential_constraints().to_vec();
+    }
+    let constraint = navigation
+      .relationship()
+      .and_then(|relationship| self.find_association(relationship))
+      .and_then(|(_, association)| association.constraint());
+    let (constraint, from_role) = match (constraint, navigation.from_role()) {
+      (Some(constraint), Some(from_role)) => (constraint, from_role),
+      _ => return Vec::new(),
+    };
+    if from_role == constraint.dependent_role() {
+      constraint.constraints().to_vec()
+    } else if from_role == constraint.principal_role() {
+      constraint
+        .constraints()
+        .iter()
+        .map(|constraint| {
+          ReferentialConstraint::new(constraint.referenced_property(), constraint.property())
+        })
+        .collect()
+    } else {
+      Vec::new()
+    }
+  }
+
+  pub fn find_base_type(&self, entity_type: &EntityType) -> Option<(&Schema, &EntityType)> {
+    self.find_entity_type(entity_type.base_type()?)
+  }
+
+  pub fn entity_type_hierarchy<'a>(
+    &'a self,
+    schema: &'a Schema,
+    entity_type: &'a EntityType,
+  ) -> Vec<(&'a Schema, &'a EntityType)> {
+    let mut hierarchy = vec![(schema, entity_type)];
+    while let Some(base) = hierarchy
+      .last()
+      .and_then(|(_, entity_type)| self.find_base_type(entity_type))
+    {
+      if hierarchy
+        .iter()
+        .any(|(_, entity_type)| std::ptr::eq(*entity_type, base.1))
+      {
+        break;
+      }
+      hierarchy.push(base);
+    }
+    hierarchy.reverse();
+    hierarchy
+  }
+
+  pub fn foreign_keys<'a>(
+    &'a self,
+    schema: &'a Schema,
+    entity_type: &'a EntityType,
+  ) -> Vec<ForeignKey<'a>> {
+    let mut foreign_keys = Vec::new();
+    for navigation in entity_type.navigation_properties() {
+      if navigation.referential_constraints().is_empty() {
+        continue;
+      }
+      if let Some(target) = self.resolve_navigation(schema, navigation) {
+        foreign_keys.push(ForeignKey::new(
+          navigation.referential_constraints().to_vec(),
+          target.schema(),
+          target.entity_type(),
+        ));
+      }
+    }
+    for association_schema in self.schemas.iter() {
+      for association in association_schema.associations() {
+        let constraint = match association.constraint() {
+          Some(constraint) => constraint,
+          None => continue,
+        };
+        let dependent = association
+          .end(constraint.dependent_role())
+          .and_then(|end| self.find_association_end_type(association_schema, end.entity_type()));
+        let principal = association
+          .end(constraint.principal_role())
+          .and_then(|end| self.find_association_end_type(association_schema, end.entity_type()));
+        if let (Some((dependent_schema, dependent)), Some((principal_schema, principal))) =
+          (dependent, principal)
+        {
+          if std::ptr::eq(dependent_schema, schema) && dependent.name() == entity_type.name() {
+            foreign_keys.push(ForeignKey::new(
+              constraint.constraints().to_vec(),
+              principal_schema,
+              principal,
+            ));
+          }
+        }
+      }
+    }
+    foreign_keys
+  }
+
+  fn find_association(&self, relationship: &str) -> Option<(&Schema, &Association)> {
+    let association_name = relationship.rsplit('.').next().unwrap_or(relationship);
+    self.schemas.iter().find_map(|schema| {
+      let name = schema.unqualify(relationship).unwrap_or(association_name);
+      schema
+        .association(name)
+        .map(|association| (schema, association))
+    })
+  }
+
+  fn find_association_end_type<'a>(
+    &'a self,
+    association_schema: &'a Schema,
+    entity_type: &str,
+  ) -> Option<(&'a Schema, &'a EntityType)> {
+    self.find_entity_type(entity_type).or_else(|| {
+      let name = entity_type.rsplit('.').next()?;
+      association_schema
+        .entity_type(name)
+        .map(|entity_type| (association_schema, entity_type))
+    })
+  }
+}
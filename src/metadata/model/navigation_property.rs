@@ -0,0 +1,154 @@
+use super::association::Multiplicity;
+use super::entity_type::EntityType;
+use super::referential_constraint::ReferentialConstraint;
+use super::schema::Schema;
+
+#[derive(Clone, Debug)]
+pub struct NavigationProperty {
+  name: String,
+  target: String,
+  collection: bool,
+  nullable: bool,
+  relationship: Option<String>,
+  from_role: Option<String>,
+  to_role: Option<String>,
+  partner: Option<String>,
+  referential_constraints: Vec<ReferentialConstraint>,
+  cds_name: Option<String>,
+}
+
+impl NavigationProperty {
+  pub fn new(name: &str, target: &str, collection: bool) -> Self {
+    NavigationProperty {
+      name: name.to_owned(),
+      target: target.to_owned(),
+      collection,
+      nullable: true,
+      relationship: None,
+      from_role: None,
+      to_role: None,
+      partner: None,
+      referential_constraints: Vec::new(),
+      cds_name: None,
+    }
+  }
+
+  pub fn with_roles(mut self, relationship: &str, from_role: &str, to_role: &str) -> Self {
+    self.relationship = Some(relationship.to_owned());
+    self.from_role = Some(from_role.to_owned());
+    self.to_role = Some(to_role.to_owned());
+    self
+  }
+
+  pub fn with_nullable(mut self, nullable: bool) -> Self {
+    self.nullable = nullable;
+    self
+  }
+
+  pub fn with_partner(mut self, partner: &str) -> Self {
+    self.partner = Some(partner.to_owned());
+    self
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn target(&self) -> &str {
+    &self.target
+  }
+
+  pub fn is_collection(&self) -> bool {
+    self.collection
+  }
+
+  pub fn nullable(&self) -> bool {
+    self.nullable
+  }
+
+  pub fn relationship(&self) -> Option<&str> {
+    self.relationship.as_deref()
+  }
+
+  pub fn from_role(&self) -> Option<&str> {
+    self.from_role.as_deref()
+  }
+
+  pub fn to_role(&self) -> Option<&str> {
+    self.to_role.as_deref()
+  }
+
+  pub fn partner(&self) -> Option<&str> {
+    self.partner.as_deref()
+  }
+
+  pub fn multiplicity(&self) -> Multiplicity {
+    if self.collection {
+      Multiplicity::Many
+    } else if self.nullable {
+      Multiplicity::ZeroOrOne
+    } else {
+      Multiplicity::One
+    }
+  }
+
+  pub fn referential_constraints(&self) -> &[ReferentialConstraint] {
+    &self.referential_constraints
+  }
+
+  pub fn add_referential_constraint(&mut self, constraint: ReferentialConstraint) {
+    self.referential_constraints.push(constraint);
+  }
+
+  pub fn cds_name(&self) -> Option<&str> {
+    self.cds_name.as_deref()
+  }
+
+  pub fn set_cds_name(&mut self, cds_name: &str) {
+    self.cds_name = Some(cds_name.to_owned());
+  }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NavigationTarget<'a> {
+  schema: &'a Schema,
+  entity_type: &'a EntityType,
+  multiplicity: Multiplicity,
+  source_multiplicity: Option<Multiplicity>,
+}
+
+impl<'a> NavigationTarget<'a> {
+  pub fn new(schema: &'a Schema, entity_type: &'a EntityType, multiplicity: Multiplicity) -> Self {
+    NavigationTarget {
+      schema,
+      entity_type,
+      multiplicity,
+      source_multiplicity: None,
+    }
+  }
+
+  pub fn with_source_multiplicity(mut self, source_multiplicity: Option<Multiplicity>) -> Self {
+    self.source_multiplicity = source_multiplicity;
+    self
+  }
+
+  pub fn schema(&self) -> &'a Schema {
+    self.schema
+  }
+
+  pub fn entity_type(&self) -> &'a EntityType {
+    self.entity_type
+  }
+
+  pub fn multiplicity(&self) -> Multiplicity {
+    self.multiplicity
+  }
+
+  pub fn source_multiplicity(&self) -> Option<Multiplicity> {
+    self.source_multiplicity
+  }
+
+  pub fn is_collection(&self) -> bool {
+    self.multiplicity == Multiplicity::Many
+  }
+}
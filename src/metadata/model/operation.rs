@@ -0,0 +1,169 @@
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeRef {
+  name: String,
+  collection: bool,
+}
+
+impl TypeRef {
+  pub fn from_odata(type_name: &str) -> Self {
+    match type_name
+      .strip_prefix("Collection(")
+      .and_then(|name| name.strip_suffix(')'))
+    {
+      Some(name) => TypeRef {
+        name: name.to_owned(),
+        collection: true,
+      },
+      None => TypeRef {
+        name: type_name.to_owned(),
+        collection: false,
+      },
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn is_collection(&self) -> bool {
+    self.collection
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationKind {
+  Function,
+  Action,
+}
+
+#[derive(Clone, Debug)]
+pub struct Parameter {
+  name: String,
+  type_ref: TypeRef,
+  nullable: bool,
+}
+
+impl Parameter {
+  pub fn new(name: &str, type_ref: TypeRef, nullable: bool) -> Self {
+    Parameter {
+      name: name.to_owned(),
+      type_ref,
+      nullable,
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn type_ref(&self) -> &TypeRef {
+    &self.type_ref
+  }
+
+  pub fn nullable(&self) -> bool {
+    self.nullable
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct Operation {
+  kind: OperationKind,
+  name: String,
+  bound: bool,
+  parameters: Vec<Parameter>,
+  return_type: Option<TypeRef>,
+  http_method: Option<String>,
+}
+
+impl Operation {
+  pub fn new(kind: OperationKind, name: &str) -> Self {
+    Operation {
+      kind,
+      name: name.to_owned(),
+      bound: false,
+      parameters: Vec::new(),
+      return_type: None,
+      http_method: None,
+    }
+  }
+
+  pub fn kind(&self) -> OperationKind {
+    self.kind
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn is_bound(&self) -> bool {
+    self.bound
+  }
+
+  pub fn set_bound(&mut self, bound: bool) {
+    self.bound = bound;
+  }
+
+  pub fn parameters(&self) -> &[Parameter] {
+    &self.parameters
+  }
+
+  pub fn same_signature(&self, other: &Operation) -> bool {
+    self.name == other.name
+      && self.bound == other.bound
+      && self.parameters.len() == other.parameters.len()
+      && self
+        .parameters
+        .iter()
+        .zip(other.parameters.iter())
+        .all(|(parameter, other)| parameter.type_ref == other.type_ref)
+  }
+
+  pub fn add_parameter(&mut self, parameter: Parameter) {
+    self.parameters.push(parameter);
+  }
+
+  pub fn return_type(&self) -> Option<&TypeRef> {
+    self.return_type.as_ref()
+  }
+
+  pub fn set_return_type(&mut self, return_type: TypeRef) {
+    self.return_type = Some(return_type);
+  }
+
+  pub fn http_method(&self) -> Option<&str> {
+    self.http_method.as_deref()
+  }
+
+  pub fn set_http_method(&mut self, http_method: &str) {
+    self.http_method = Some(http_method.to_owned());
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct OperationImport {
+  name: String,
+  operation: String,
+  entity_set: Option<String>,
+}
+
+impl OperationImport {
+  pub fn new(name: &str, operation: &str, entity_set: Option<&str>) -> Self {
+    OperationImport {
+      name: name.to_owned(),
+      operation: operation.to_owned(),
+      entity_set: entity_set.map(str::to_owned),
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn operation(&self) -> &str {
+    &self.operation
+  }
+
+  pub fn entity_set(&self) -> Option<&str> {
+    self.entity_set.as_deref()
+  }
+}
@@ -0,0 +1,114 @@
+use super::cds_type::CdsType;
+use super::edm_type::EdmType;
+use super::facets::Facets;
+use super::value_list::ValueList;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PropertyType {
+  Edm(EdmType),
+  Complex(String),
+  Enum(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct Property {
+  name: String,
+  property_type: PropertyType,
+  collection: bool,
+  facets: Facets,
+  value_lists: Vec<ValueList>,
+  value_list_with_fixed_values: bool,
+  original_type: Option<String>,
+  cds_type: Option<CdsType>,
+  cds_name: Option<String>,
+}
+
+impl Property {
+  pub fn new(name: &str, property_type: PropertyType, facets: Facets) -> Self {
+    Property {
+      name: name.to_owned(),
+      property_type,
+      collection: false,
+      facets,
+      value_lists: Vec::new(),
+      value_list_with_fixed_values: false,
+      original_type: None,
+      cds_type: None,
+      cds_name: None,
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn property_type(&self) -> &PropertyType {
+    &self.property_type
+  }
+
+  pub fn set_property_type(&mut self, property_type: PropertyType) {
+    self.property_type = property_type;
+  }
+
+  pub fn edm_type(&self) -> Option<EdmType> {
+    match self.property_type {
+      PropertyType::Edm(edm_type) => Some(edm_type),
+      _ => None,
+    }
+  }
+
+  pub fn is_collection(&self) -> bool {
+    self.collection
+  }
+
+  pub fn set_collection(&mut self, collection: bool) {
+    self.collection = collection;
+  }
+
+  pub fn facets(&self) -> &Facets {
+    &self.facets
+  }
+
+  pub fn value_lists(&self) -> &[ValueList] {
+    &self.value_lists
+  }
+
+  pub fn set_value_list(&mut self, value_list: ValueList) {
+    self
+      .value_lists
+      .retain(|current| current.qualifier() != value_list.qualifier());
+    self.value_lists.push(value_list);
+  }
+
+  pub fn value_list_with_fixed_values(&self) -> bool {
+    self.value_list_with_fixed_values
+  }
+
+  pub fn set_value_list_with_fixed_values(&mut self, fixed_values: bool) {
+    self.value_list_with_fixed_values = fixed_values;
+  }
+
+  pub fn original_type(&self) -> Option<&str> {
+    self.original_type.as_deref()
+  }
+
+  pub fn set_original_type(&mut self, original_type: &str) {
+    self.original_type = Some(original_type.to_owned());
+  }
+
+  pub fn cds_type(&self) -> Option<&CdsType> {
+    self.cds_type.as_ref()
+  }
+
+  pub fn set_cds_type(&mut self, cds_type: CdsType) {
+    self.cds_type = Some(cds_type);
+  }
+
+  pub fn cds_name(&self) -> Option<&str> {
+    self.cds_name.as_deref()
+  }
+
+  pub fn set_cds_name(&mut self, cds_name: &str) {
+    self.cds_name = Some(cds_name.to_owned());
+  }
+}
@@ -0,0 +1,91 @@
+use super::entity_type::EntityType;
+use super::schema::Schema;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReferentialConstraint {
+  property: String,
+  referenced_property: String,
+}
+
+impl ReferentialConstraint {
+  pub fn new(property: &str, referenced_property: &str) -> Self {
+    ReferentialConstraint {
+      property: property.to_owned(),
+      referenced_property: referenced_property.to_owned(),
+    }
+  }
+
+  pub fn property(&self) -> &str {
+    &self.property
+  }
+
+  pub fn referenced_property(&self) -> &str {
+    &self.referenced_property
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct AssociationConstraint {
+  principal_role: String,
+  dependent_role: String,
+  constraints: Vec<ReferentialConstraint>,
+}
+
+impl AssociationConstraint {
+  pub fn new(
+    principal_role: &str,
+    dependent_role: &str,
+    constraints: Vec<ReferentialConstraint>,
+  ) -> Self {
+    AssociationConstraint {
+      principal_role: principal_role.to_owned(),
+      dependent_role: dependent_role.to_owned(),
+      constraints,
+    }
+  }
+
+  pub fn principal_role(&self) -> &str {
+    &self.principal_role
+  }
+
+  pub fn dependent_role(&self) -> &str {
+    &self.dependent_role
+  }
+
+  pub fn constraints(&self) -> &[ReferentialConstraint] {
+    &self.constraints
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct ForeignKey<'a> {
+  constraints: Vec<ReferentialConstraint>,
+  referenced_schema: &'a Schema,
+  referenced_entity_type: &'a EntityType,
+}
+
+impl<'a> ForeignKey<'a> {
+  pub fn new(
+    constraints: Vec<ReferentialConstraint>,
+    referenced_schema: &'a Schema,
+    referenced_entity_type: &'a EntityType,
+  ) -> Self {
+    ForeignKey {
+      constraints,
+      referenced_schema,
+      referenced_entity_type,
+    }
+  }
+
+  pub fn constraints(&self) -> &[ReferentialConstraint] {
+    &self.constraints
+  }
+
+  pub fn referenced_schema(&self) -> &'a Schema {
+    self.referenced_schema
+  }
+
+  pub fn referenced_entity_type(&self) -> &'a EntityType {
+    self.referenced_entity_type
+  }
+}
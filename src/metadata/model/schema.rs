@@ -0,0 +1,236 @@
+use super::association::Association;
+use super::complex_type::ComplexType;
+use super::entity_set::EntitySet;
+use super::entity_type::EntityType;
+use super::enum_type::EnumType;
+use super::operation::{Operation, OperationImport};
+
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+  namespace: String,
+  alias: Option<String>,
+  entity_container: Option<String>,
+  entity_types: Vec<EntityType>,
+  complex_types: Vec<ComplexType>,
+  enum_types: Vec<EnumType>,
+  associations: Vec<Association>,
+  entity_sets: Vec<EntitySet>,
+  operations: Vec<Operation>,
+  operation_imports: Vec<OperationImport>,
+}
+
+impl Schema {
+  pub fn new(namespace: &str) -> Self {
+    Schema {
+      namespace: namespace.to_owned(),
+      ..Default::default()
+    }
+  }
+
+  pub fn namespace(&self) -> &str {
+    &self.namespace
+  }
+
+  pub fn alias(&self) -> Option<&str> {
+    self.alias.as_deref()
+  }
+
+  pub fn set_alias(&mut self, alias: &str) {
+    self.alias = Some(alias.to_owned());
+  }
+
+  pub fn qualify(&self, name: &str) -> String {
+    if self.namespace.is_empty() {
+      name.to_owned()
+    } else {
+      format!("{}.{}", self.namespace, name)
+    }
+  }
+
+  pub fn unqualify<'a>(&self, name: &'a str) -> Option<&'a str> {
+    let namespace_prefix = name.strip_prefix(self.namespace.as_str());
+    let alias_prefix = self
+      .alias
+      .as_ref()
+      .and_then(|a| name.strip_prefix(a.as_str()));
+    namespace_prefix
+      .or(alias_prefix)
+      .and_then(|name| name.strip_prefix('.'))
+  }
+
+  pub fn entity_container(&self) -> Option<&str> {
+    self.entity_container.as_deref()
+  }
+
+  pub fn set_entity_container(&mut self, name: &str) {
+    self.entity_container = Some(name.to_owned());
+  }
+
+  pub fn entity_types(&self) -> &[EntityType] {
+    &self.entity_types
+  }
+
+  pub fn entity_types_mut(&mut self) -> &mut Vec<EntityType> {
+    &mut self.entity_types
+  }
+
+  pub fn entity_type(&self, name: &str) -> Option<&EntityType> {
+    self
+      .entity_types
+      .iter()
+      .find(|entity| entity.name() == name)
+  }
+
+  pub fn add_entity_type(&mut self, entity_type: EntityType) {
+    self.entity_types.push(entity_type);
+  }
+
+  pub fn complex_types(&self) -> &[ComplexType] {
+    &self.complex_types
+  }
+
+  pub fn complex_types_mut(&mut self) -> &mut Vec<ComplexType> {
+    &mut self.complex_types
+  }
+
+  pub fn complex_type(&self, name: &str) -> Option<&ComplexType> {
+    self
+      .complex_types
+      .iter()
+      .find(|complex_type| complex_type.name() == name)
+  }
+
+  pub fn add_complex_type(&mut self, complex_type: ComplexType) {
+    self.complex_types.push(complex_type);
+  }
+
+  pub fn enum_types(&self) -> &[EnumType] {
+    &self.enum_types
+  }
+
+  pub fn enum_types_mut(&mut self) -> &mut Vec<EnumType> {
+    &mut self.enum_types
+  }
+
+  pub fn enum_type(&self, name: &str) -> Option<&EnumType> {
+    self
+      .enum_types
+      .iter()
+      .find(|enum_type| enum_type.name() == name)
+  }
+
+  pub fn add_enum_type(&mut self, enum_type: EnumType) {
+    self.enum_types.push(enum_type);
+  }
+
+  pub fn associations(&self) -> &[Association] {
+    &self.associations
+  }
+
+  pub fn associations_mut(&mut self) -> &mut Vec<Association> {
+    &mut self.associations
+  }
+
+  pub fn association(&self, name: &str) -> Option<&Association> {
+    self
+      .associations
+      .iter()
+      .find(|association| association.name() == name)
+  }
+
+  pub fn add_association(&mut self, association: Association) {
+    self.associations.push(association);
+  }
+
+  pub fn entity_sets(&self) -> &[EntitySet] {
+    &self.entity_sets
+  }
+
+  pub fn entity_sets_mut(&mut self) -> &mut Vec<EntitySet> {
+    &mut self.entity_sets
+  }
+
+  pub fn add_entity_set(&mut self, entity_set: EntitySet) {
+    self.entity_sets.push(entity_set);
+  }
+
+  pub fn operations(&self) -> &[Operation] {
+    &self.operations
+  }
+
+  pub fn operations_mut(&mut self) -> &mut Vec<Operation> {
+    &mut self.operations
+  }
+
+  pub fn add_operation(&mut self, operation: Operation) {
+    self.operations.push(operation);
+  }
+
+  pub fn operation_imports(&self) -> &[OperationImport] {
+    &self.operation_imports
+  }
+
+  pub fn operation_imports_mut(&mut self) -> &mut Vec<OperationImport> {
+    &mut self.operation_imports
+  }
+
+  pub fn add_operation_import(&mut self, operation_import: OperationImport) {
+    self.operation_imports.push(operation_import);
+  }
+
+  pub fn merge(&mut self, other: Schema) {
+    if self.alias.is_none() {
+      self.alias = other.alias;
+    }
+    if self.entity_container.is_none() {
+      self.entity_container = other.entity_container;
+    }
+    for entity_type in other.entity_types {
+      if self.entity_type(entity_type.name()).is_none() {
+        self.entity_types.push(entity_type);
+      }
+    }
+    for complex_type in other.complex_types {
+      if self.complex_type(complex_type.name()).is_none() {
+        self.complex_types.push(complex_type);
+      }
+    }
+    for enum_type in other.enum_types {
+      if self.enum_type(enum_type.name()).is_none() {
+        self.enum_types.push(enum_type);
+      }
+    }
+    for association in other.associations {
+      if self.association(association.name()).is_none() {
+        self.associations.push(association);
+      }
+    }
+    for entity_set in other.entity_sets {
+      if !self
+        .entity_sets
+        .iter()
+        .any(|existing| existing.name() == entity_set.name())
+      {
+        self.entity_sets.push(entity_set);
+      }
+    }
+    for operation in other.operations {
+      if !self
+        .operations
+        .iter()
+        .any(|existing| existing.same_signature(&operation))
+      {
+        self.operations.push(operation);
+      }
+    }
+    for operation_import in other.operation_imports {
+      if !self
+        .operation_imports
+        .iter()
+        .any(|existing| existing.name() == operation_import.name())
+      {
+        self.operation_imports.push(operation_import);
+      }
+    }
+  }
+}
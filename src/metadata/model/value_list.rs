@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FMTResult};
+
+#[derive(Clone, Debug, Default)]
+pub struct ValueList {
+  qualifier: Option<String>,
+  collection_path: String,
+  label: Option<String>,
+  search_supported: Option<bool>,
+  parameters: Vec<ValueListParameter>,
+}
+
+impl ValueList {
+  pub fn is_value_list_term(term: &str) -> bool {
+    term.rsplit('.').next() == Some("ValueList")
+  }
+
+  pub fn set_property(&mut self, property: &str, attributes: &HashMap<String, String>) {
+    match property {
+      "CollectionPath" => {
+        if let Some(value) = property_value(attributes) {
+          self.collection_path = value.to_owned();
+        }
+      }
+      "Label" => self.label = property_value(attributes).cloned(),
+      "SearchSupported" => {
+        self.search_supported = property_value(attributes).map(|value| value == "true")
+      }
+      _ => (),
+    }
+  }
+
+  pub fn set_qualifier(&mut self, qualifier: &str) {
+    self.qualifier = Some(qualifier.to_owned());
+  }
+
+  pub fn add_parameter(&mut self, parameter: ValueListParameter) {
+    self.parameters.push(parameter);
+  }
+
+  pub fn qualifier(&self) -> Option<&str> {
+    self.qualifier.as_deref()
+  }
+
+  pub fn collection_path(&self) -> &str {
+    &self.collection_path
+  }
+
+  pub fn label(&self) -> Option<&str> {
+    self.label.as_deref()
+  }
+
+  pub fn search_supported(&self) -> Option<bool> {
+    self.search_supported
+  }
+
+  pub fn parameters(&self) -> &[ValueListParameter] {
+    &self.parameters
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct ValueListParameter {
+  kind: ValueListParameterKind,
+  local_data_property: Option<String>,
+  value_list_property: String,
+  constant: Option<String>,
+}
+
+impl ValueListParameter {
+  pub fn from_record_type(record_type: &str) -> Option<Self> {
+    let kind = match record_type.rsplit('.').next()? {
+      "ValueListParameterIn" => ValueListParameterKind::In,
+      "ValueListParameterOut" => ValueListParameterKind::Out,
+      "ValueListParameterInOut" => ValueListParameterKind::InOut,
+      "ValueListParameterDisplayOnly" => ValueListParameterKind::DisplayOnly,
+      "ValueListParameterConstant" => ValueListParameterKind::Constant,
+      "ValueListParameterFilterOnly" => ValueListParameterKind::FilterOnly,
+      _ => return None,
+    };
+    Some(ValueListParameter {
+      kind,
+      local_data_property: None,
+      value_list_property: String::new(),
+      constant: None,
+    })
+  }
+
+  pub fn set_property(&mut self, property: &str, attributes: &HashMap<String, String>) {
+    match property {
+      "LocalDataProperty" => self.local_data_property = property_value(attributes).cloned(),
+      "ValueListProperty" => {
+        if let Some(value) = property_value(attributes) {
+          self.value_list_property = value.to_owned();
+        }
+      }
+      "Constant" => self.constant = property_value(attributes).cloned(),
+      _ => (),
+    }
+  }
+
+  pub fn kind(&self) -> ValueListParameterKind {
+    self.kind
+  }
+
+  pub fn local_data_property(&self) -> Option<&str> {
+    self.local_data_property.as_deref()
+  }
+
+  pub fn value_list_property(&self) -> Option<&str> {
+    if self.value_list_property.is_empty() {
+      None
+    } else {
+      Some(&self.value_list_property)
+    }
+  }
+
+  pub fn constant(&self) -> Option<&str> {
+    self.constant.as_deref()
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueListParameterKind {
+  In,
+  Out,
+  InOut,
+  DisplayOnly,
+  Constant,
+  FilterOnly,
+}
+
+impl Display for ValueListParameterKind {
+  fn fmt(&self, fmt: &mut Formatter) -> FMTResult {
+    let kind = match self {
+      ValueListParameterKind::In => "ValueListParameterIn",
+      ValueListParameterKind::Out => "ValueListParameterOut",
+      ValueListParameterKind::InOut => "ValueListParameterInOut",
+      ValueListParameterKind::DisplayOnly => "ValueListParameterDisplayOnly",
+      ValueListParameterKind::Constant => "ValueListParameterConstant",
+      ValueListParameterKind::FilterOnly => "ValueListParameterFilterOnly",
+    };
+    write!(fmt, "{}", kind)
+  }
+}
+
+fn property_value(attributes: &HashMap<String, String>) -> Option<&String> {
+  attributes
+    .get("String")
+    .or_else(|| attributes.get("PropertyPath"))
+    .or_else(|| attributes.get("Path"))
+    .or_else(|| attributes.get("Bool"))
+}
@@ -0,0 +1,27 @@
+use clap::ValueEnum;
+use std::fmt::{Display, Formatter, Result as FMTResult};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ODataVersion {
+  V2,
+  V4,
+}
+
+impl ODataVersion {
+  pub fn from_edmx_version(version: &str) -> Option<Self> {
+    match version.split('.').next()? {
+      "1" | "2" | "3" => Some(Self::V2),
+      "4" => Some(Self::V4),
+      _ => None,
+    }
+  }
+}
+
+impl Display for ODataVersion {
+  fn fmt(&self, fmt: &mut Formatter) -> FMTResult {
+    match self {
+      Self::V2 => write!(fmt, "v2"),
+      Self::V4 => write!(fmt, "v4"),
+    }
+  }
+}
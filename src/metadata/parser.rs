@@ -1,77 +1,272 @@
+use super::super::xml_tags::types::Position;
 use super::super::xml_tags::types::Tag;
 use super::super::xml_tags::types::TagEvent;
 use super::super::xml_tags::types::TagParser;
-use super::cds::entity::Entity;
-use super::cds::field::Field;
-use super::error::ParserError;
+use super::error::{ParserError, ParserErrorKind};
+use super::model::{
+  Association, AssociationConstraint, AssociationEnd, ComplexType, EdmType, Element, EntitySet,
+  EntityType, EnumType, Facets, Model, Multiplicity, NavigationProperty, ODataVersion, Operation,
+  OperationImport, OperationKind, Parameter, Property, PropertyType, ReferentialConstraint, Scale,
+  Schema, TypeRef, ValueList, ValueListParameter,
+};
 use std::collections::HashMap;
 use std::error::Error;
 
 pub struct Parser {
-  finished_entities: Vec<Entity>,
-  entity_name: String,
-  fields: HashMap<String, Field>,
-  fields_order: Vec<String>,
-  keys: Vec<String>,
-  field_name: String,
-  field_type: String,
-  associated_target: String,
-  field_attributes: HashMap<String, String>,
-  schema_name: String,
+  version: Option<ODataVersion>,
+  schemas: Vec<Schema>,
+  schema: Option<Schema>,
+  entity_type: Option<EntityType>,
+  complex_type: Option<ComplexType>,
+  enum_type: Option<EnumType>,
+  association: Option<Association>,
+  constraint_role: String,
+  constraint_properties: Option<Vec<String>>,
+  constraint_principal: Option<(String, Vec<String>)>,
+  constraint_dependent: Option<(String, Vec<String>)>,
+  property_name: String,
+  property_attributes: HashMap<String, String>,
+  property_value_lists: Vec<ValueList>,
+  navigation_property: Option<NavigationProperty>,
+  operation: Option<Operation>,
+  annotations_target: String,
+  value_list: Option<ValueList>,
+  value_list_has_record: bool,
+  value_list_parameter: Option<ValueListParameter>,
+  value_list_depth: usize,
+  value_list_position: Option<Position>,
+  targeted_value_lists: Vec<(String, ValueList, Option<Position>)>,
   tag_parser: Option<Box<dyn TagParser>>,
+  lenient: bool,
+  warnings: Vec<ParserError>,
+  position: Option<Position>,
+  skip_depth: usize,
 }
 
 impl Parser {
   pub fn new(tag_parser: Box<dyn TagParser>) -> Parser {
     Parser {
-      finished_entities: Vec::new(),
-      entity_name: String::new(),
-      fields: HashMap::new(),
-      fields_order: Vec::new(),
-      keys: Vec::new(),
-      field_name: String::new(),
-      field_type: String::new(),
-      associated_target: String::new(),
-      field_attributes: HashMap::new(),
-      schema_name: String::new(),
+      version: None,
+      schemas: Vec::new(),
+      schema: None,
+      entity_type: None,
+      complex_type: None,
+      enum_type: None,
+      association: None,
+      constraint_role: String::new(),
+      constraint_properties: None,
+      constraint_principal: None,
+      constraint_dependent: None,
+      property_name: String::new(),
+      property_attributes: HashMap::new(),
+      property_value_lists: Vec::new(),
+      navigation_property: None,
+      operation: None,
+      annotations_target: String::new(),
+      value_list: None,
+      value_list_has_record: false,
+      value_list_parameter: None,
+      value_list_depth: 0,
+      value_list_position: None,
+      targeted_value_lists: Vec::new(),
       tag_parser: Some(tag_parser),
+      lenient: false,
+      warnings: Vec::new(),
+      position: None,
+      skip_depth: 0,
     }
   }
 
-  pub fn parse(&mut self) -> Result<String, Box<dyn Error>> {
+  pub fn with_lenient(mut self, lenient: bool) -> Parser {
+    self.lenient = lenient;
+    self
+  }
+
+  pub fn take_warnings(&mut self) -> Vec<ParserError> {
+    std::mem::take(&mut self.warnings)
+  }
+
+  pub fn parse(&mut self) -> Result<Model, Box<dyn Error>> {
     if let Some(tag_parser) = self.tag_parser.take() {
       for e in tag_parser {
-        match e {
-          Ok(TagEvent::Open { tag, attributes }) => match tag {
-            Tag::Schema => self.on_schema_start(&attributes)?,
-            Tag::EntityType => self.on_entity_start(&attributes)?,
-            Tag::Property => self.on_property_start(&attributes)?,
-            Tag::NavigationProperty => self.on_navigation_property_start(&attributes)?,
-            Tag::PropertyRef => self.on_property_ref(attributes)?,
-          },
-          Ok(TagEvent::Close { tag }) => match tag {
-            Tag::EntityType => self.on_entity_close()?,
-            Tag::Property => self.on_property_close()?,
-            Tag::NavigationProperty => self.on_navigation_property_close(),
-            _ => (),
-          },
-          Err(e) => {
-            return Err(Box::new(e));
+        let (tag, is_open, result) = match e {
+          Ok(TagEvent::Open {
+            tag,
+            attributes,
+            position,
+          }) => {
+            self.position = position;
+            if self.skip_depth > 0 {
+              self.skip_depth += 1;
+              continue;
+            }
+            (tag, true, self.on_open_tag(tag, attributes))
+          }
+          Ok(TagEvent::Close { tag, position }) => {
+            self.position = position;
+            if self.skip_depth > 0 {
+              self.skip_depth -= 1;
+              continue;
+            }
+            (tag, false, self.on_close_tag(tag))
+          }
+          Err(e) => return Err(Box::new(e.with_context(self.context()))),
+        };
+        if let Err(error) = result {
+          let error = self.locate(ParserError::downcast(error)?);
+          if !self.lenient {
+            return Err(Box::new(error));
+          }
+          self.warnings.push(error);
+          self.discard(tag);
+          if is_open {
+            self.skip_depth = 1;
           }
         }
       }
     }
-    Ok(self.compose_cds_string())
+    self.position = None;
+    self.on_schema_close();
+    self.apply_targeted_value_lists();
+    let mut model = Model::new(std::mem::take(&mut self.schemas));
+    if let Some(version) = self.version {
+      model.set_version(version);
+    }
+    self.resolve_named_types(&mut model)?;
+    Ok(model)
+  }
+
+  fn on_open_tag(
+    &mut self,
+    tag: Tag,
+    attributes: HashMap<String, String>,
+  ) -> Result<(), Box<dyn Error>> {
+    match tag {
+      Tag::Edmx => self.on_edmx_start(&attributes),
+      Tag::Schema => self.on_schema_start(&attributes)?,
+      Tag::EntityType => self.on_entity_start(&attributes)?,
+      Tag::Property => self.on_property_start(&attributes)?,
+      Tag::NavigationProperty => self.on_navigation_property_start(&attributes)?,
+      Tag::PropertyRef => self.on_property_ref(attributes)?,
+      Tag::Annotations => self.on_annotations_start(&attributes)?,
+      Tag::Annotation => self.on_annotation_start(&attributes),
+      Tag::Record => self.on_record_start(&attributes),
+      Tag::PropertyValue => self.on_property_value(&attributes),
+      Tag::EntityContainer => self.on_entity_container_start(&attributes)?,
+      Tag::EntitySet => self.on_entity_set(&attributes)?,
+      Tag::FunctionImport => self.on_function_import_start(&attributes)?,
+      Tag::ActionImport => self.on_action_import(&attributes)?,
+      Tag::Function => self.on_operation_start(OperationKind::Function, &attributes)?,
+      Tag::Action => self.on_operation_start(OperationKind::Action, &attributes)?,
+      Tag::Parameter => self.on_parameter(&attributes)?,
+      Tag::ReturnType => self.on_return_type(&attributes)?,
+      Tag::ComplexType => self.on_complex_type_start(&attributes)?,
+      Tag::EnumType => self.on_enum_type_start(&attributes)?,
+      Tag::Member => self.on_member(&attributes)?,
+      Tag::Association => self.on_association_start(&attributes)?,
+      Tag::End => self.on_association_end(&attributes)?,
+      Tag::ReferentialConstraint => self.on_referential_constraint_start(&attributes),
+      Tag::Principal => self.on_constraint_end_start("Principal", &attributes)?,
+      Tag::Dependent => self.on_constraint_end_start("Dependent", &attributes)?,
+    }
+    Ok(())
+  }
+
+  fn on_close_tag(&mut self, tag: Tag) -> Result<(), Box<dyn Error>> {
+    match tag {
+      Tag::Schema => self.on_schema_close(),
+      Tag::EntityType => self.on_entity_close()?,
+      Tag::Property => self.on_property_close()?,
+      Tag::NavigationProperty => self.on_navigation_property_close(),
+      Tag::Annotations => self.annotations_target.clear(),
+      Tag::Annotation => self.on_annotation_close(),
+      Tag::Record => self.on_record_close(),
+      Tag::FunctionImport | Tag::Function | Tag::Action => self.on_operation_close(),
+      Tag::ComplexType => self.on_complex_type_close(),
+      Tag::EnumType => self.on_enum_type_close(),
+      Tag::Association => self.on_association_close(),
+      Tag::ReferentialConstraint => self.on_referential_constraint_close(),
+      Tag::Principal => self.constraint_principal = self.take_constraint_end(),
+      Tag::Dependent => self.constraint_dependent = self.take_constraint_end(),
+      _ => (),
+    }
+    Ok(())
+  }
+
+  fn locate(&self, error: ParserError) -> ParserError {
+    error
+      .with_position(self.position)
+      .with_context(self.context())
+  }
+
+  fn recover(&mut self, error: Box<ParserError>) -> Result<(), Box<dyn Error>> {
+    if !self.lenient {
+      return Err(error);
+    }
+    let warning = self.locate(*error);
+    self.warnings.push(warning);
+    Ok(())
+  }
+
+  fn discard(&mut self, tag: Tag) {
+    match tag {
+      Tag::Property => {
+        self.property_name.clear();
+        self.property_attributes.clear();
+        self.property_value_lists.clear();
+      }
+      Tag::NavigationProperty => self.navigation_property = None,
+      Tag::Annotations => self.annotations_target.clear(),
+      _ => (),
+    }
+  }
+
+  fn context(&self) -> Option<String> {
+    let mut context = Vec::new();
+    if let Some(entity_type) = self.entity_type.as_ref() {
+      context.push(format!("entity type {}", entity_type.name()));
+    } else if let Some(complex_type) = self.complex_type.as_ref() {
+      context.push(format!("complex type {}", complex_type.name()));
+    } else if let Some(enum_type) = self.enum_type.as_ref() {
+      context.push(format!("enum type {}", enum_type.name()));
+    } else if let Some(association) = self.association.as_ref() {
+      context.push(format!("association {}", association.name()));
+    } else if let Some(operation) = self.operation.as_ref() {
+      context.push(format!("operation {}", operation.name()));
+    }
+    if !self.property_name.is_empty() {
+      context.push(format!("property {}", self.property_name));
+    } else if let Some(navigation_property) = self.navigation_property.as_ref() {
+      context.push(format!(
+        "navigation property {}",
+        navigation_property.name()
+      ));
+    }
+    if context.is_empty() {
+      None
+    } else {
+      Some(context.join(", "))
+    }
+  }
+
+  fn on_edmx_start(&mut self, attributes: &HashMap<String, String>) {
+    self.version = attributes
+      .get("Version")
+      .and_then(|version| ODataVersion::from_edmx_version(version));
   }
 
   fn on_schema_start(
     &mut self,
     attributes: &HashMap<String, String>,
   ) -> Result<(), Box<dyn Error>> {
-    self.schema_name = attributes
+    let namespace = attributes
       .get("Namespace")
-      .ok_or_else(|| ParserError::new_boxed("Failed to get schema name"))?
-      .to_string();
+      .ok_or_else(|| ParserError::missing_attribute("Schema", "Namespace"))?;
+    let mut schema = Schema::new(namespace);
+    if let Some(alias) = attributes.get("Alias") {
+      schema.set_alias(alias);
+    }
+    self.schema = Some(schema);
     Ok(())
   }
 
@@ -79,10 +274,14 @@ impl Parser {
     &mut self,
     attributes: &HashMap<String, String>,
   ) -> Result<(), Box<dyn Error>> {
-    self.entity_name = attributes
+    let name = attributes
       .get("Name")
-      .ok_or_else(|| ParserError::new_boxed("Failed to get entity's name"))?
-      .to_string();
+      .ok_or_else(|| ParserError::missing_attribute("EntityType", "Name"))?;
+    let mut entity_type = EntityType::new(name);
+    if let Some(base_type) = attributes.get("BaseType") {
+      entity_type.set_base_type(base_type);
+    }
+    self.entity_type = Some(entity_type);
     Ok(())
   }
 
@@ -90,15 +289,14 @@ impl Parser {
     &mut self,
     attributes: &HashMap<String, String>,
   ) -> Result<(), Box<dyn Error>> {
-    self.field_name = attributes
+    self.property_name = attributes
       .get("Name")
-      .ok_or_else(|| ParserError::new_boxed("Failed to get property's name"))?
+      .ok_or_else(|| ParserError::missing_attribute("Property", "Name"))?
       .to_string();
-    self.field_type = attributes
-      .get("Type")
-      .ok_or_else(|| ParserError::new_boxed("Failed to get property's type"))?
-      .to_string();
-    self.field_attributes = attributes.clone();
+    if !attributes.contains_key("Type") {
+      return Err(ParserError::missing_attribute("Property", "Type"));
+    }
+    self.property_attributes = attributes.clone();
     Ok(())
   }
 
@@ -106,78 +304,647 @@ impl Parser {
     &mut self,
     attributes: &HashMap<String, String>,
   ) -> Result<(), Box<dyn Error>> {
-    self.field_name = attributes
+    let name = attributes
       .get("Name")
-      .ok_or_else(|| ParserError::new_boxed("Failed to get nav. property's name"))?
-      .to_string();
-    self.associated_target = attributes
+      .ok_or_else(|| ParserError::missing_attribute("NavigationProperty", "Name"))?;
+    let target = attributes
       .get("ToRole")
-      .ok_or_else(|| ParserError::new_boxed("Failed to get nav. property's target"))?
-      .to_string();
-    if !self.schema_name.is_empty() {
-      self.associated_target = self
-        .associated_target
-        .replace(&(self.schema_name.clone() + "."), "");
+      .or_else(|| attributes.get("Type"))
+      .ok_or_else(|| {
+        let attribute = if attributes.contains_key("Relationship") {
+          "ToRole"
+        } else {
+          "Type"
+        };
+        ParserError::missing_attribute("NavigationProperty", attribute)
+      })?;
+    let target = TypeRef::from_odata(target);
+    let target_name = self
+      .schema
+      .as_ref()
+      .and_then(|schema| schema.unqualify(target.name()))
+      .unwrap_or_else(|| target.name());
+    let mut navigation_property =
+      NavigationProperty::new(name, target_name, target.is_collection());
+    if let (Some(relationship), Some(from_role), Some(to_role)) = (
+      attributes.get("Relationship"),
+      attributes.get("FromRole"),
+      attributes.get("ToRole"),
+    ) {
+      navigation_property = navigation_property.with_roles(relationship, from_role, to_role);
+    }
+    if let Some(partner) = attributes.get("Partner") {
+      navigation_property = navigation_property.with_partner(partner);
+    }
+    if attributes.get("Nullable").map(String::as_str) == Some("false") {
+      navigation_property = navigation_property.with_nullable(false);
     }
-    self.associated_target = self.associated_target.replace("Collection(", "");
-    self.associated_target = self.associated_target.replace(')', "");
+    self.navigation_property = Some(navigation_property);
     Ok(())
   }
 
   fn on_property_ref(&mut self, attributes: HashMap<String, String>) -> Result<(), Box<dyn Error>> {
     let field_name = attributes
       .get("Name")
-      .ok_or_else(|| ParserError::new_boxed("Failed to get property ref's name"))?
+      .ok_or_else(|| ParserError::missing_attribute("PropertyRef", "Name"))?;
+    if let Some(properties) = self.constraint_properties.as_mut() {
+      properties.push(field_name.to_owned());
+    } else if let Some(entity_type) = self.entity_type.as_mut() {
+      entity_type.add_key(field_name);
+    }
+    Ok(())
+  }
+
+  fn on_annotations_start(
+    &mut self,
+    attributes: &HashMap<String, String>,
+  ) -> Result<(), Box<dyn Error>> {
+    self.annotations_target = attributes
+      .get("Target")
+      .ok_or_else(|| ParserError::missing_attribute("Annotations", "Target"))?
+      .to_string();
+    Ok(())
+  }
+
+  fn on_annotation_start(&mut self, attributes: &HashMap<String, String>) {
+    if self.value_list.is_some() {
+      self.value_list_depth += 1;
+      return;
+    }
+    let is_value_list = attributes
+      .get("Term")
+      .map(|term| ValueList::is_value_list_term(term))
+      .unwrap_or(false);
+    if is_value_list {
+      let mut value_list = ValueList::default();
+      if let Some(qualifier) = attributes.get("Qualifier") {
+        value_list.set_qualifier(qualifier);
+      }
+      self.value_list = Some(value_list);
+      self.value_list_has_record = false;
+      self.value_list_depth = 0;
+      self.value_list_position = self.position;
+    }
+  }
+
+  fn on_record_start(&mut self, attributes: &HashMap<String, String>) {
+    if self.value_list.is_none() || self.value_list_depth > 0 {
+      return;
+    }
+    if !self.value_list_has_record {
+      self.value_list_has_record = true;
+      return;
+    }
+    self.value_list_parameter = attributes
+      .get("Type")
+      .and_then(|record_type| ValueListParameter::from_record_type(record_type));
+  }
+
+  fn on_property_value(&mut self, attributes: &HashMap<String, String>) {
+    let property = match attributes.get("Property") {
+      Some(property) if self.value_list_depth == 0 => property,
+      _ => return,
+    };
+    if let Some(parameter) = self.value_list_parameter.as_mut() {
+      parameter.set_property(property, attributes);
+    } else if let Some(value_list) = self.value_list.as_mut() {
+      value_list.set_property(property, attributes);
+    }
+  }
+
+  fn on_record_close(&mut self) {
+    if self.value_list_depth > 0 {
+      return;
+    }
+    if let Some(parameter) = self.value_list_parameter.take() {
+      if let Some(value_list) = self.value_list.as_mut() {
+        value_list.add_parameter(parameter);
+      }
+    }
+  }
+
+  fn on_annotation_close(&mut self) {
+    if self.value_list_depth > 0 {
+      self.value_list_depth -= 1;
+      return;
+    }
+    let value_list = match self.value_list.take() {
+      Some(value_list) => value_list,
+      None => return,
+    };
+    if self.annotations_target.is_empty() {
+      if !self.property_name.is_empty() {
+        self.property_value_lists.push(value_list);
+      }
+      return;
+    }
+    self.targeted_value_lists.push((
+      self.annotations_target.clone(),
+      value_list,
+      self.value_list_position,
+    ));
+  }
+
+  fn on_entity_container_start(
+    &mut self,
+    attributes: &HashMap<String, String>,
+  ) -> Result<(), Box<dyn Error>> {
+    let name = attributes
+      .get("Name")
+      .ok_or_else(|| ParserError::missing_attribute("EntityContainer", "Name"))?;
+    self.current_schema().set_entity_container(name);
+    Ok(())
+  }
+
+  fn on_entity_set(&mut self, attributes: &HashMap<String, String>) -> Result<(), Box<dyn Error>> {
+    let name = attributes
+      .get("Name")
+      .ok_or_else(|| ParserError::missing_attribute("EntitySet", "Name"))?;
+    let entity_type = attributes
+      .get("EntityType")
+      .ok_or_else(|| ParserError::missing_attribute("EntitySet", "EntityType"))?;
+    self
+      .current_schema()
+      .add_entity_set(EntitySet::new(name, entity_type));
+    Ok(())
+  }
+
+  fn on_function_import_start(
+    &mut self,
+    attributes: &HashMap<String, String>,
+  ) -> Result<(), Box<dyn Error>> {
+    let name = attributes
+      .get("Name")
+      .ok_or_else(|| ParserError::missing_attribute("FunctionImport", "Name"))?;
+    let entity_set = attributes.get("EntitySet").map(String::as_str);
+    if let Some(function) = attributes.get("Function") {
+      let operation_import = OperationImport::new(name, function, entity_set);
+      self.current_schema().add_operation_import(operation_import);
+      return Ok(());
+    }
+    let http_method = attributes.get("HttpMethod");
+    let kind = match http_method.map(String::as_str) {
+      None | Some("GET") => OperationKind::Function,
+      Some(_) => OperationKind::Action,
+    };
+    let mut operation = Operation::new(kind, name);
+    if let Some(http_method) = http_method {
+      operation.set_http_method(http_method);
+    }
+    if let Some(return_type) = attributes.get("ReturnType") {
+      operation.set_return_type(TypeRef::from_odata(return_type));
+    }
+    let qualified_name = self.current_schema().qualify(name);
+    let operation_import = OperationImport::new(name, &qualified_name, entity_set);
+    self.current_schema().add_operation_import(operation_import);
+    self.operation = Some(operation);
+    Ok(())
+  }
+
+  fn on_action_import(
+    &mut self,
+    attributes: &HashMap<String, String>,
+  ) -> Result<(), Box<dyn Error>> {
+    let name = attributes
+      .get("Name")
+      .ok_or_else(|| ParserError::missing_attribute("ActionImport", "Name"))?;
+    let action = attributes
+      .get("Action")
+      .ok_or_else(|| ParserError::missing_attribute("ActionImport", "Action"))?;
+    let entity_set = attributes.get("EntitySet").map(String::as_str);
+    let operation_import = OperationImport::new(name, action, entity_set);
+    self.current_schema().add_operation_import(operation_import);
+    Ok(())
+  }
+
+  fn on_operation_start(
+    &mut self,
+    kind: OperationKind,
+    attributes: &HashMap<String, String>,
+  ) -> Result<(), Box<dyn Error>> {
+    let name = attributes.get("Name").ok_or_else(|| {
+      let element = match kind {
+        OperationKind::Function => "Function",
+        OperationKind::Action => "Action",
+      };
+      ParserError::missing_attribute(element, "Name")
+    })?;
+    let mut operation = Operation::new(kind, name);
+    operation.set_bound(attributes.get("IsBound").map(String::as_str) == Some("true"));
+    self.operation = Some(operation);
+    Ok(())
+  }
+
+  fn on_parameter(&mut self, attributes: &HashMap<String, String>) -> Result<(), Box<dyn Error>> {
+    let operation = match self.operation.as_mut() {
+      Some(operation) => operation,
+      None => return Ok(()),
+    };
+    let name = attributes
+      .get("Name")
+      .ok_or_else(|| ParserError::missing_attribute("Parameter", "Name"))?;
+    let type_name = attributes
+      .get("Type")
+      .ok_or_else(|| ParserError::missing_attribute("Parameter", "Type"))?;
+    let nullable = attributes.get("Nullable").map(String::as_str) != Some("false");
+    operation.add_parameter(Parameter::new(
+      name,
+      TypeRef::from_odata(type_name),
+      nullable,
+    ));
+    Ok(())
+  }
+
+  fn on_return_type(&mut self, attributes: &HashMap<String, String>) -> Result<(), Box<dyn Error>> {
+    if let Some(operation) = self.operation.as_mut() {
+      let type_name = attributes
+        .get("Type")
+        .ok_or_else(|| ParserError::missing_attribute("ReturnType", "Type"))?;
+      operation.set_return_type(TypeRef::from_odata(type_name));
+    }
+    Ok(())
+  }
+
+  fn on_operation_close(&mut self) {
+    if let Some(operation) = self.operation.take() {
+      self.current_schema().add_operation(operation);
+    }
+  }
+
+  fn on_complex_type_start(
+    &mut self,
+    attributes: &HashMap<String, String>,
+  ) -> Result<(), Box<dyn Error>> {
+    let name = attributes
+      .get("Name")
+      .ok_or_else(|| ParserError::missing_attribute("ComplexType", "Name"))?;
+    let mut complex_type = ComplexType::new(name);
+    if let Some(base_type) = attributes.get("BaseType") {
+      complex_type.set_base_type(base_type);
+    }
+    self.complex_type = Some(complex_type);
+    Ok(())
+  }
+
+  fn on_complex_type_close(&mut self) {
+    if let Some(complex_type) = self.complex_type.take() {
+      self.current_schema().add_complex_type(complex_type);
+    }
+  }
+
+  fn on_enum_type_start(
+    &mut self,
+    attributes: &HashMap<String, String>,
+  ) -> Result<(), Box<dyn Error>> {
+    let name = attributes
+      .get("Name")
+      .ok_or_else(|| ParserError::missing_attribute("EnumType", "Name"))?;
+    let underlying_type = match attributes.get("UnderlyingType") {
+      Some(underlying_type) => EdmType::from_name(underlying_type)
+        .ok_or_else(|| ParserError::unknown_type(underlying_type))?,
+      None => EdmType::Int32,
+    };
+    let is_flags = attributes.get("IsFlags").map(String::as_str) == Some("true");
+    self.enum_type = Some(EnumType::new(name, underlying_type, is_flags));
+    Ok(())
+  }
+
+  fn on_member(&mut self, attributes: &HashMap<String, String>) -> Result<(), Box<dyn Error>> {
+    if let Some(enum_type) = self.enum_type.as_mut() {
+      let name = attributes
+        .get("Name")
+        .ok_or_else(|| ParserError::missing_attribute("Member", "Name"))?;
+      let value = attributes.get("Value").and_then(|value| value.parse().ok());
+      enum_type.add_member(name, value);
+    }
+    Ok(())
+  }
+
+  fn on_enum_type_close(&mut self) {
+    if let Some(enum_type) = self.enum_type.take() {
+      self.current_schema().add_enum_type(enum_type);
+    }
+  }
+
+  fn on_association_start(
+    &mut self,
+    attributes: &HashMap<String, String>,
+  ) -> Result<(), Box<dyn Error>> {
+    let name = attributes
+      .get("Name")
+      .ok_or_else(|| ParserError::missing_attribute("Association", "Name"))?;
+    self.association = Some(Association::new(name));
+    Ok(())
+  }
+
+  fn on_association_end(
+    &mut self,
+    attributes: &HashMap<String, String>,
+  ) -> Result<(), Box<dyn Error>> {
+    let association = match self.association.as_mut() {
+      Some(association) => association,
+      None => return Ok(()),
+    };
+    let role = attributes
+      .get("Role")
+      .ok_or_else(|| ParserError::missing_attribute("End", "Role"))?;
+    let entity_type = attributes
+      .get("Type")
+      .ok_or_else(|| ParserError::missing_attribute("End", "Type"))?;
+    let multiplicity = attributes
+      .get("Multiplicity")
+      .and_then(|multiplicity| Multiplicity::from_odata(multiplicity))
+      .ok_or_else(|| ParserError::missing_attribute("End", "Multiplicity"))?;
+    association.add_end(AssociationEnd::new(role, entity_type, multiplicity));
+    Ok(())
+  }
+
+  fn on_referential_constraint_start(&mut self, attributes: &HashMap<String, String>) {
+    if let (Some(navigation_property), Some(property), Some(referenced_property)) = (
+      self.navigation_property.as_mut(),
+      attributes.get("Property"),
+      attributes.get("ReferencedProperty"),
+    ) {
+      navigation_property
+        .add_referential_constraint(ReferentialConstraint::new(property, referenced_property));
+    }
+  }
+
+  fn on_constraint_end_start(
+    &mut self,
+    element: &str,
+    attributes: &HashMap<String, String>,
+  ) -> Result<(), Box<dyn Error>> {
+    if self.association.is_none() {
+      return Ok(());
+    }
+    self.constraint_role = attributes
+      .get("Role")
+      .ok_or_else(|| ParserError::missing_attribute(element, "Role"))?
       .to_string();
-    self.keys.push(field_name);
+    self.constraint_properties = Some(Vec::new());
     Ok(())
   }
 
+  fn take_constraint_end(&mut self) -> Option<(String, Vec<String>)> {
+    let properties = self.constraint_properties.take()?;
+    Some((std::mem::take(&mut self.constraint_role), properties))
+  }
+
+  fn on_referential_constraint_close(&mut self) {
+    let (principal, dependent) = match (
+      self.constraint_principal.take(),
+      self.constraint_dependent.take(),
+    ) {
+      (Some(principal), Some(dependent)) => (principal, dependent),
+      _ => return,
+    };
+    if let Some(association) = self.association.as_mut() {
+      let constraints = dependent
+        .1
+        .iter()
+        .zip(principal.1.iter())
+        .map(|(property, referenced_property)| {
+          ReferentialConstraint::new(property, referenced_property)
+        })
+        .collect();
+      association.set_constraint(AssociationConstraint::new(
+        &principal.0,
+        &dependent.0,
+        constraints,
+      ));
+    }
+  }
+
+  fn on_association_close(&mut self) {
+    if let Some(association) = self.association.take() {
+      self.current_schema().add_association(association);
+    }
+  }
+
   fn on_entity_close(&mut self) -> Result<(), Box<dyn Error>> {
-    for key in self.keys.iter() {
-      self
-        .fields
-        .get_mut(key)
-        .ok_or_else(|| ParserError::new_boxed("Unknown property in property ref"))?
-        .set_as_key()
-    }
-    let entity_fields: Vec<_> = self
-      .fields_order
-      .iter()
-      .map(|name| self.fields.get(name).unwrap().clone())
-      .collect();
-    let entity = Entity::new(&self.entity_name, &entity_fields);
-    self.keys.clear();
-    self.finished_entities.push(entity);
-    self.entity_name.clear();
-    self.fields.clear();
-    self.fields_order.clear();
+    let (entity, unknown_keys): (String, Vec<_>) = match self.entity_type.as_ref() {
+      Some(entity_type) => (
+        entity_type.name().to_owned(),
+        entity_type
+          .keys()
+          .iter()
+          .filter(|key| !entity_type.elements().iter().any(|e| e.name() == *key))
+          .cloned()
+          .collect(),
+      ),
+      None => return Ok(()),
+    };
+    for key in unknown_keys {
+      self.recover(ParserError::new_boxed(
+        ParserErrorKind::UnknownKeyProperty {
+          entity: entity.clone(),
+          key: key.clone(),
+        },
+      ))?;
+      if let Some(entity_type) = self.entity_type.as_mut() {
+        entity_type.remove_key(&key);
+      }
+    }
+    if let Some(entity_type) = self.entity_type.take() {
+      self.current_schema().add_entity_type(entity_type);
+    }
     Ok(())
   }
 
   fn on_property_close(&mut self) -> Result<(), Box<dyn Error>> {
-    let field = Field::from_odata(&self.field_name, &self.field_type, &self.field_attributes)?;
-    self.fields.insert(self.field_name.clone(), field);
-    self.fields_order.push(self.field_name.clone());
-    self.field_name.clear();
-    self.field_type.clear();
-    self.field_attributes.clear();
+    let mut property = self.build_property()?;
+    for value_list in self.property_value_lists.drain(..) {
+      property.set_value_list(value_list);
+    }
+    if let Some(entity_type) = self.entity_type.as_mut() {
+      entity_type.add_element(Element::Property(property));
+    } else if let Some(complex_type) = self.complex_type.as_mut() {
+      complex_type.add_property(property);
+    }
+    self.property_name.clear();
+    self.property_attributes.clear();
     Ok(())
   }
 
   fn on_navigation_property_close(&mut self) {
-    let field = Field::new_association(&self.field_name, &self.associated_target);
-    self.fields.insert(self.field_name.clone(), field);
-    self.fields_order.push(self.field_name.clone());
-    self.associated_target.clear();
-    self.field_name.clear();
+    if let (Some(entity_type), Some(navigation_property)) =
+      (self.entity_type.as_mut(), self.navigation_property.take())
+    {
+      entity_type.add_element(Element::NavigationProperty(navigation_property));
+    }
+  }
+
+  fn on_schema_close(&mut self) {
+    if let Some(schema) = self.schema.take() {
+      self.schemas.push(schema);
+    }
+  }
+
+  fn current_schema(&mut self) -> &mut Schema {
+    self.schema.get_or_insert_with(Schema::default)
   }
 
-  fn compose_cds_string(&self) -> String {
-    let mut cds = String::from("");
-    for entity in self.finished_entities.iter() {
-      cds.push_str(&entity.to_cds());
+  fn build_property(&mut self) -> Result<Property, Box<dyn Error>> {
+    let attributes = std::mem::take(&mut self.property_attributes);
+    let odata_type = attributes
+      .get("Type")
+      .ok_or_else(|| ParserError::missing_attribute("Property", "Type"))?;
+    let type_ref = TypeRef::from_odata(odata_type);
+    let mut original_type = None;
+    let property_type = if type_ref.name().starts_with("Edm.") {
+      match EdmType::from_name(type_ref.name()) {
+        Some(edm_type) => PropertyType::Edm(edm_type),
+        None => {
+          self.recover(ParserError::unknown_type(type_ref.name()))?;
+          original_type = Some(type_ref.name());
+          PropertyType::Edm(EdmType::String)
+        }
+      }
+    } else {
+      PropertyType::Complex(type_ref.name().to_owned())
+    };
+    let precision = attributes
+      .get("precision")
+      .or_else(|| attributes.get("Precision"));
+    let scale = attributes.get("scale").or_else(|| attributes.get("Scale"));
+    if property_type == PropertyType::Edm(EdmType::Decimal) {
+      if precision.is_none() {
+        self.recover(ParserError::missing_attribute("Property", "Precision"))?;
+      } else if scale.is_none() {
+        self.recover(ParserError::missing_attribute("Property", "Scale"))?;
+      }
+    }
+    let facets = Facets::new(
+      attributes.get("Nullable").map(String::as_str) != Some("false"),
+      attributes
+        .get("MaxLength")
+        .and_then(|length| length.parse().ok()),
+      precision.and_then(|precision| precision.parse().ok()),
+      scale.and_then(|scale| Scale::from_odata(scale)),
+    );
+    let mut property = Property::new(&self.property_name, property_type, facets);
+    if let Some(original_type) = original_type {
+      property.set_original_type(original_type);
+    }
+    property.set_collection(type_ref.is_collection());
+    property.set_value_list_with_fixed_values(
+      attributes.get("value-list").map(String::as_str) == Some("fixed-values"),
+    );
+    Ok(property)
+  }
+
+  fn apply_targeted_value_lists(&mut self) {
+    for (target, value_list, position) in std::mem::take(&mut self.targeted_value_lists) {
+      let property = target.rsplit_once('/').and_then(|(path, property_name)| {
+        let (schema_index, entity_name) = self.find_target_entity_type(path)?;
+        self.schemas[schema_index]
+          .entity_types_mut()
+          .iter_mut()
+          .find(|entity_type| entity_type.name() == entity_name)?
+          .property_mut(property_name)
+      });
+      match property {
+        Some(property) => property.set_value_list(value_list),
+        None => self.warnings.push(
+          ParserError::new(ParserErrorKind::UnknownAnnotationTarget { target })
+            .with_position(position),
+        ),
+      }
     }
-    cds
+  }
+
+  fn find_target_entity_type(&self, path: &str) -> Option<(usize, String)> {
+    let entity_path = match path.split_once('/') {
+      Some((container, entity_set)) => self.schemas.iter().find_map(|schema| {
+        let container = if container.contains('.') {
+          schema.unqualify(container)?
+        } else {
+          container
+        };
+        if schema.entity_container() != Some(container) {
+          return None;
+        }
+        schema
+          .entity_sets()
+          .iter()
+          .find(|set| set.name() == entity_set)
+          .map(|set| set.entity_type().to_owned())
+      })?,
+      None => path.to_owned(),
+    };
+    self.schemas.iter().enumerate().find_map(|(index, schema)| {
+      let entity_name = if entity_path.contains('.') {
+        schema.unqualify(&entity_path)?
+      } else {
+        &entity_path
+      };
+      schema
+        .entity_type(entity_name)
+        .map(|entity_type| (index, entity_type.name().to_owned()))
+    })
+  }
+
+  fn resolve_named_types(&mut self, model: &mut Model) -> Result<(), Box<dyn Error>> {
+    let mut resolved_types = HashMap::new();
+    let mut unknown_types = Vec::new();
+    for schema in model.schemas() {
+      let entity_properties = schema.entity_types().iter().flat_map(|entity_type| {
+        entity_type
+          .properties()
+          .map(move |property| (format!("entity type {}", entity_type.name()), property))
+      });
+      let complex_properties = schema.complex_types().iter().flat_map(|complex_type| {
+        complex_type
+          .properties()
+          .iter()
+          .map(move |property| (format!("complex type {}", complex_type.name()), property))
+      });
+      for (owner, property) in entity_properties.chain(complex_properties) {
+        if let PropertyType::Complex(name) = property.property_type() {
+          let resolved_type = if let Some((schema, enum_type)) = model.find_enum_type(name) {
+            PropertyType::Enum(schema.qualify(enum_type.name()))
+          } else if let Some((schema, complex_type)) = model.find_complex_type(name) {
+            PropertyType::Complex(schema.qualify(complex_type.name()))
+          } else {
+            unknown_types.push((
+              name.clone(),
+              format!("{}, property {}", owner, property.name()),
+            ));
+            continue;
+          };
+          resolved_types.insert(name.clone(), resolved_type);
+        }
+      }
+    }
+    for (name, context) in unknown_types {
+      let error = ParserError::unknown_type(&name).with_context(Some(context));
+      self.recover(Box::new(error))?;
+    }
+    let resolve = |property: &mut Property| {
+      if let PropertyType::Complex(name) = property.property_type() {
+        match resolved_types.get(name) {
+          Some(resolved_type) => property.set_property_type(resolved_type.clone()),
+          None => {
+            let name = name.clone();
+            property.set_property_type(PropertyType::Edm(EdmType::String));
+            property.set_original_type(&name);
+          }
+        }
+      }
+    };
+    for schema in model.schemas_mut() {
+      for entity_type in schema.entity_types_mut() {
+        for element in entity_type.elements_mut() {
+          if let Element::Property(property) = element {
+            resolve(property);
+          }
+        }
+      }
+      for complex_type in schema.complex_types_mut() {
+        complex_type.properties_mut().iter_mut().for_each(resolve);
+      }
+    }
+    Ok(())
   }
 }
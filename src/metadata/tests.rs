@@ -1,8 +1,13 @@
+use super::super::renderers::{quoted_identifiers, CdlRenderer, Renderer};
+use super::super::xml_tags::types::Position;
 use super::super::xml_tags::types::Tag;
-use super::super::xml_tags::types::TagError;
 use super::super::xml_tags::types::TagEvent;
 use super::super::xml_tags::types::TagParser;
-use super::Parser;
+use super::model::{EdmType, Model, OperationKind, ReferentialConstraint, Scale};
+use super::{
+  diff, validate, Change, EntityFilter, Parser, ParserError, ParserErrorKind, Severity,
+  ValidationIssue,
+};
 use std::collections::HashMap;
 use std::error::Error;
 
@@ -11,6 +16,7 @@ macro_rules! open_tag {
       TagEvent::Open {
         tag: $tag_type,
         attributes: HashMap::from([$(map_spec!($key, $val)),*]),
+        position: None,
       }
     };
   }
@@ -21,7 +27,10 @@ macro_rules! map_spec {
 }
 macro_rules! close_tag {
   ($tag_type: expr) => {
-    TagEvent::Close { tag: $tag_type }
+    TagEvent::Close {
+      tag: $tag_type,
+      position: None,
+    }
   };
 }
 
@@ -502,38 +511,759 @@ fn with_missing_decimal_scale_or_precision_it_panics() {
   ];
   let result = parse(tags);
   if let Err(error) = result {
-    assert_eq!(error.to_string(), "Metadata Parser Error, reason: Failed to parse a Decimal type, scale or precision is missing");
+    assert_eq!(
+      error.to_string(),
+      "Metadata Parser Error, reason: Missing attribute 'Precision' of Property\n  = note: in entity type FeaturedProduct, property TotalExpense"
+    );
     return;
   }
   panic!("Missed a parsing error")
 }
 
+#[test]
+fn with_value_list_annotations_it_generates_value_helps() {
+  let tags = vec![
+    open_tag!(Tag::Schema, ("Namespace", "test")),
+    open_tag!(Tag::EntityType, ("Name", "Order")),
+    open_tag!(Tag::PropertyRef, ("Name", "ID")),
+    close_tag!(Tag::PropertyRef),
+    open_tag!(Tag::Property, ("Name", "ID"), ("Type", "Edm.Int32")),
+    close_tag!(Tag::Property),
+    open_tag!(
+      Tag::Property,
+      ("Name", "CompanyCode"),
+      ("Type", "Edm.String"),
+      ("MaxLength", "4"),
+      ("value-list", "standard")
+    ),
+    close_tag!(Tag::Property),
+    open_tag!(
+      Tag::Property,
+      ("Name", "Status"),
+      ("Type", "Edm.String"),
+      ("value-list", "fixed-values")
+    ),
+    open_tag!(
+      Tag::Annotation,
+      ("Term", "com.sap.vocabularies.Common.v1.ValueList")
+    ),
+    open_tag!(Tag::Record,),
+    open_tag!(
+      Tag::PropertyValue,
+      ("Property", "CollectionPath"),
+      ("String", "Statuses")
+    ),
+    close_tag!(Tag::PropertyValue),
+    open_tag!(Tag::PropertyValue, ("Property", "Parameters")),
+    open_tag!(
+      Tag::Record,
+      (
+        "Type",
+        "com.sap.vocabularies.Common.v1.ValueListParameterOut"
+      )
+    ),
+    open_tag!(
+      Tag::PropertyValue,
+      ("Property", "LocalDataProperty"),
+      ("PropertyPath", "Status")
+    ),
+    close_tag!(Tag::PropertyValue),
+    open_tag!(
+      Tag::PropertyValue,
+      ("Property", "ValueListProperty"),
+      ("String", "Code")
+    ),
+    close_tag!(Tag::PropertyValue),
+    close_tag!(Tag::Record),
+    close_tag!(Tag::PropertyValue),
+    close_tag!(Tag::Record),
+    close_tag!(Tag::Annotation),
+    close_tag!(Tag::Property),
+    close_tag!(Tag::EntityType),
+    open_tag!(Tag::Annotations, ("Target", "test.Order/CompanyCode")),
+    open_tag!(Tag::Annotation, ("Term", "Common.ValueList")),
+    open_tag!(Tag::Record,),
+    open_tag!(
+      Tag::PropertyValue,
+      ("Property", "Label"),
+      ("String", "Company")
+    ),
+    close_tag!(Tag::PropertyValue),
+    open_tag!(
+      Tag::PropertyValue,
+      ("Property", "CollectionPath"),
+      ("String", "VL_SH_H_T001")
+    ),
+    close_tag!(Tag::PropertyValue),
+    open_tag!(Tag::PropertyValue, ("Property", "Parameters")),
+    open_tag!(Tag::Record, ("Type", "Common.ValueListParameterInOut")),
+    open_tag!(
+      Tag::PropertyValue,
+      ("Property", "LocalDataProperty"),
+      ("PropertyPath", "CompanyCode")
+    ),
+    close_tag!(Tag::PropertyValue),
+    open_tag!(
+      Tag::PropertyValue,
+      ("Property", "ValueListProperty"),
+      ("String", "Bukrs")
+    ),
+    close_tag!(Tag::PropertyValue),
+    close_tag!(Tag::Record),
+    open_tag!(
+      Tag::Record,
+      ("Type", "Common.ValueListParameterDisplayOnly")
+    ),
+    open_tag!(
+      Tag::PropertyValue,
+      ("Property", "ValueListProperty"),
+      ("String", "Butxt")
+    ),
+    close_tag!(Tag::PropertyValue),
+    close_tag!(Tag::Record),
+    close_tag!(Tag::PropertyValue),
+    close_tag!(Tag::Record),
+    close_tag!(Tag::Annotation),
+    close_tag!(Tag::Annotations),
+    close_tag!(Tag::Schema),
+  ];
+  let cds = parse(tags).unwrap();
+  assert_eq!(
+    cds,
+    "entity ![Order] {
+  key ID: Integer;
+  @Common.ValueList: {
+    CollectionPath: 'VL_SH_H_T001',
+    Label: 'Company',
+    Parameters: [
+      {
+        $Type: 'Common.ValueListParameterInOut',
+        LocalDataProperty: CompanyCode,
+        ValueListProperty: 'Bukrs'
+      },
+      {
+        $Type: 'Common.ValueListParameterDisplayOnly',
+        ValueListProperty: 'Butxt'
+      }
+    ]
+  }
+  CompanyCode: String(4);
+  @Common.ValueList: {
+    CollectionPath: 'Statuses',
+    Parameters: [
+      {
+        $Type: 'Common.ValueListParameterOut',
+        LocalDataProperty: Status,
+        ValueListProperty: 'Code'
+      }
+    ]
+  }
+  @Common.ValueListWithFixedValues
+  Status: String;
+}
+"
+  );
+}
+
+#[test]
+fn with_reserved_or_invalid_names_it_quotes_identifiers() {
+  let tags = vec![
+    open_tag!(Tag::Schema, ("Namespace", "test")),
+    open_tag!(Tag::EntityType, ("Name", "Type")),
+    open_tag!(Tag::PropertyRef, ("Name", "Key")),
+    close_tag!(Tag::PropertyRef),
+    open_tag!(Tag::Property, ("Name", "Key"), ("Type", "Edm.Int32")),
+    close_tag!(Tag::Property),
+    open_tag!(Tag::Property, ("Name", "Net-Price"), ("Type", "Edm.Double")),
+    close_tag!(Tag::Property),
+    open_tag!(Tag::Property, ("Name", "1stLine"), ("Type", "Edm.Boolean")),
+    close_tag!(Tag::Property),
+    open_tag!(
+      Tag::Property,
+      ("Name", "Description"),
+      ("Type", "Edm.Boolean")
+    ),
+    close_tag!(Tag::Property),
+    open_tag!(
+      Tag::NavigationProperty,
+      ("Name", "Entity"),
+      ("Type", "test.Type")
+    ),
+    close_tag!(Tag::NavigationProperty),
+    close_tag!(Tag::EntityType),
+    close_tag!(Tag::Schema),
+  ];
+  let model = parse_model(tags).unwrap();
+
+  assert_eq!(
+    CdlRenderer.render(&model).unwrap(),
+    "entity ![Type] {
+  key ![Key]: Integer;
+  ![Net-Price]: Double;
+  ![1stLine]: Boolean;
+  Description: Boolean;
+  ![Entity]: Association to ![Type] on ...;
+}
+"
+  );
+  assert_eq!(
+    quoted_identifiers(&model),
+    [
+      "entity type test.Type is written as ![Type], it is a reserved word in CDS",
+      "property Key of entity type test.Type is written as ![Key], it is a reserved word in CDS",
+      "property Net-Price of entity type test.Type is written as ![Net-Price], it is not a valid CDS identifier",
+      "property 1stLine of entity type test.Type is written as ![1stLine], it is not a valid CDS identifier",
+      "navigation property Entity of entity type test.Type is written as ![Entity], it is a reserved word in CDS",
+    ]
+  );
+}
+
+#[test]
+fn with_usual_input_it_builds_typed_model() {
+  let tags = vec![
+    open_tag!(Tag::Schema, ("Namespace", "test")),
+    open_tag!(Tag::EntityType, ("Name", "Product")),
+    open_tag!(Tag::PropertyRef, ("Name", "ID")),
+    close_tag!(Tag::PropertyRef),
+    open_tag!(
+      Tag::Property,
+      ("Name", "ID"),
+      ("Type", "Edm.Int32"),
+      ("Nullable", "false")
+    ),
+    close_tag!(Tag::Property),
+    open_tag!(
+      Tag::Property,
+      ("Name", "Price"),
+      ("Type", "Edm.Decimal"),
+      ("Precision", "15"),
+      ("Scale", "variable")
+    ),
+    close_tag!(Tag::Property),
+    open_tag!(
+      Tag::NavigationProperty,
+      ("Name", "Category"),
+      ("Type", "test.Category"),
+      ("Partner", "Products")
+    ),
+    close_tag!(Tag::NavigationProperty),
+    close_tag!(Tag::EntityType),
+    open_tag!(Tag::EntityContainer, ("Name", "Container")),
+    open_tag!(
+      Tag::EntitySet,
+      ("Name", "Products"),
+      ("EntityType", "test.Product")
+    ),
+    close_tag!(Tag::EntitySet),
+    open_tag!(
+      Tag::FunctionImport,
+      ("Name", "Discontinue"),
+      ("ReturnType", "Collection(test.Product)"),
+      ("EntitySet", "Products"),
+      ("HttpMethod", "POST")
+    ),
+    open_tag!(Tag::Parameter, ("Name", "ID"), ("Type", "Edm.Int32")),
+    close_tag!(Tag::Parameter),
+    close_tag!(Tag::FunctionImport),
+    close_tag!(Tag::EntityContainer),
+    close_tag!(Tag::Schema),
+  ];
+  let model = parse_model(tags).unwrap();
+
+  let (schema, product) = model.find_entity_type("test.Product").unwrap();
+  assert_eq!(schema.entity_container(), Some("Container"));
+  assert_eq!(product.keys(), ["ID"]);
+  let id = product.property("ID").unwrap();
+  assert_eq!(id.edm_type(), Some(EdmType::Int32));
+  assert!(!id.facets().nullable());
+  let price = product.property("Price").unwrap();
+  assert_eq!(price.facets().precision(), Some(15));
+  assert_eq!(price.facets().scale(), Some(Scale::Variable));
+  let category = product.navigation_properties().next().unwrap();
+  assert_eq!(category.target(), "Category");
+  assert_eq!(category.partner(), Some("Products"));
+  assert_eq!(schema.entity_sets()[0].entity_type(), "test.Product");
+  let operation = &schema.operations()[0];
+  assert_eq!(operation.kind(), OperationKind::Action);
+  assert_eq!(operation.parameters()[0].type_ref().name(), "Edm.Int32");
+  assert!(operation.return_type().unwrap().is_collection());
+  let operation_import = &schema.operation_imports()[0];
+  assert_eq!(operation_import.operation(), "test.Discontinue");
+  assert_eq!(operation_import.entity_set(), Some("Products"));
+}
+
+#[test]
+fn with_merged_schemas_it_keeps_one_operation_per_overload() {
+  let tags = |category_type: &str| {
+    vec![
+      open_tag!(Tag::Schema, ("Namespace", "test")),
+      open_tag!(Tag::Function, ("Name", "TopProducts")),
+      open_tag!(Tag::Parameter, ("Name", "Count"), ("Type", "Edm.Int32")),
+      close_tag!(Tag::Parameter),
+      close_tag!(Tag::Function),
+      open_tag!(Tag::Function, ("Name", "TopProducts")),
+      open_tag!(Tag::Parameter, ("Name", "Count"), ("Type", "Edm.Int32")),
+      close_tag!(Tag::Parameter),
+      open_tag!(
+        Tag::Parameter,
+        ("Name", "Category"),
+        ("Type", category_type)
+      ),
+      close_tag!(Tag::Parameter),
+      close_tag!(Tag::Function),
+      close_tag!(Tag::Schema),
+    ]
+  };
+  let mut model = parse_model(tags("Edm.String")).unwrap();
+  model.merge(parse_model(tags("Edm.String")).unwrap());
+  assert_eq!(model.schemas()[0].operations().len(), 2);
+
+  model.merge(parse_model(tags("Edm.Guid")).unwrap());
+  let operations = model.schemas()[0].operations();
+  assert_eq!(operations.len(), 3);
+  assert_eq!(operations[2].parameters()[1].type_ref().name(), "Edm.Guid");
+}
+
+#[test]
+fn with_association_constraints_it_builds_foreign_keys() {
+  let tags = vec![
+    open_tag!(Tag::Schema, ("Namespace", "test")),
+    open_tag!(Tag::EntityType, ("Name", "Header")),
+    open_tag!(Tag::PropertyRef, ("Name", "ID")),
+    close_tag!(Tag::PropertyRef),
+    open_tag!(Tag::Property, ("Name", "ID"), ("Type", "Edm.String")),
+    close_tag!(Tag::Property),
+    close_tag!(Tag::EntityType),
+    open_tag!(Tag::EntityType, ("Name", "Item")),
+    open_tag!(Tag::PropertyRef, ("Name", "ID")),
+    close_tag!(Tag::PropertyRef),
+    open_tag!(Tag::Property, ("Name", "ID"), ("Type", "Edm.String")),
+    close_tag!(Tag::Property),
+    open_tag!(Tag::Property, ("Name", "HeaderID"), ("Type", "Edm.String")),
+    close_tag!(Tag::Property),
+    close_tag!(Tag::EntityType),
+    open_tag!(Tag::Association, ("Name", "HeaderItems")),
+    open_tag!(
+      Tag::End,
+      ("Type", "test.Header"),
+      ("Multiplicity", "1"),
+      ("Role", "FromHeader")
+    ),
+    close_tag!(Tag::End),
+    open_tag!(
+      Tag::End,
+      ("Type", "test.Item"),
+      ("Multiplicity", "*"),
+      ("Role", "ToItem")
+    ),
+    close_tag!(Tag::End),
+    open_tag!(Tag::ReferentialConstraint,),
+    open_tag!(Tag::Principal, ("Role", "FromHeader")),
+    open_tag!(Tag::PropertyRef, ("Name", "ID")),
+    close_tag!(Tag::PropertyRef),
+    close_tag!(Tag::Principal),
+    open_tag!(Tag::Dependent, ("Role", "ToItem")),
+    open_tag!(Tag::PropertyRef, ("Name", "HeaderID")),
+    close_tag!(Tag::PropertyRef),
+    close_tag!(Tag::Dependent),
+    close_tag!(Tag::ReferentialConstraint),
+    close_tag!(Tag::Association),
+    close_tag!(Tag::Schema),
+  ];
+  let model = parse_model(tags).unwrap();
+
+  let (schema, header) = model.find_entity_type("test.Header").unwrap();
+  assert_eq!(header.keys(), ["ID"]);
+  assert!(model.foreign_keys(schema, header).is_empty());
+  let (schema, item) = model.find_entity_type("test.Item").unwrap();
+  assert_eq!(item.keys(), ["ID"]);
+  let foreign_keys = model.foreign_keys(schema, item);
+  assert_eq!(foreign_keys.len(), 1);
+  assert_eq!(foreign_keys[0].referenced_entity_type().name(), "Header");
+  assert_eq!(
+    foreign_keys[0].constraints(),
+    [ReferentialConstraint::new("HeaderID", "ID")]
+  );
+}
+
+#[test]
+fn with_entity_filter_it_drops_dangling_associations() {
+  let tags = vec![
+    open_tag!(Tag::Schema, ("Namespace", "test")),
+    open_tag!(Tag::EntityType, ("Name", "Header")),
+    open_tag!(Tag::PropertyRef, ("Name", "ID")),
+    close_tag!(Tag::PropertyRef),
+    open_tag!(Tag::Property, ("Name", "ID"), ("Type", "Edm.String")),
+    close_tag!(Tag::Property),
+    open_tag!(
+      Tag::NavigationProperty,
+      ("Name", "Items"),
+      ("Relationship", "test.HeaderItems"),
+      ("FromRole", "FromHeader"),
+      ("ToRole", "ToItem")
+    ),
+    close_tag!(Tag::NavigationProperty),
+    close_tag!(Tag::EntityType),
+    open_tag!(Tag::EntityType, ("Name", "Item")),
+    open_tag!(Tag::PropertyRef, ("Name", "ID")),
+    close_tag!(Tag::PropertyRef),
+    open_tag!(Tag::Property, ("Name", "ID"), ("Type", "Edm.String")),
+    close_tag!(Tag::Property),
+    close_tag!(Tag::EntityType),
+    open_tag!(Tag::Association, ("Name", "HeaderItems")),
+    open_tag!(
+      Tag::End,
+      ("Type", "test.Header"),
+      ("Multiplicity", "1"),
+      ("Role", "FromHeader")
+    ),
+    close_tag!(Tag::End),
+    open_tag!(
+      Tag::End,
+      ("Type", "test.Item"),
+      ("Multiplicity", "*"),
+      ("Role", "ToItem")
+    ),
+    close_tag!(Tag::End),
+    close_tag!(Tag::Association),
+    open_tag!(Tag::EntityContainer, ("Name", "Container")),
+    open_tag!(
+      Tag::EntitySet,
+      ("Name", "Headers"),
+      ("EntityType", "test.Header")
+    ),
+    close_tag!(Tag::EntitySet),
+    open_tag!(
+      Tag::EntitySet,
+      ("Name", "Items"),
+      ("EntityType", "test.Item")
+    ),
+    close_tag!(Tag::EntitySet),
+    close_tag!(Tag::EntityContainer),
+    close_tag!(Tag::Schema),
+  ];
+  let mut model = parse_model(tags).unwrap();
+
+  EntityFilter::default()
+    .with_exclude("It*")
+    .apply(&mut model)
+    .unwrap();
+  let schema = &model.schemas()[0];
+  assert_eq!(schema.entity_types().len(), 1);
+  assert!(schema.associations().is_empty());
+  assert_eq!(schema.entity_sets().len(), 1);
+  assert_eq!(schema.entity_sets()[0].name(), "Headers");
+  assert_eq!(schema.entity_types()[0].navigation_properties().count(), 0);
+  assert!(validate(&model)
+    .iter()
+    .all(|issue| issue.severity() == Severity::Warning));
+}
+
+#[test]
+fn with_positioned_events_it_reports_the_error_location() {
+  let tags = vec![
+    Ok(open_tag!(Tag::Schema, ("Namespace", "test"))),
+    Ok(open_tag!(Tag::ComplexType, ("Name", "Address"))),
+    Ok(TagEvent::Open {
+      tag: Tag::Property,
+      attributes: HashMap::from([map_spec!("Name", "City")]),
+      position: Some(Position::new(12, 9)),
+    }),
+    Err(xml_syntax_error(Position::new(13, 1))),
+  ];
+  let error = build_parser(tags).parse().unwrap_err();
+  let error = ParserError::downcast(error).unwrap();
+  assert!(matches!(
+    error.kind(),
+    ParserErrorKind::MissingAttribute { element, attribute } if element == "Property" && attribute == "Type"
+  ));
+  assert_eq!(error.line(), Some(12));
+  assert_eq!(error.column(), Some(9));
+  assert_eq!(error.context(), Some("complex type Address, property City"));
+
+  let tags = vec![
+    Ok(open_tag!(Tag::Schema, ("Namespace", "test"))),
+    Ok(open_tag!(Tag::EntityType, ("Name", "Tests"))),
+    Err(xml_syntax_error(Position::new(3, 1))),
+  ];
+  let error = build_parser(tags).parse().unwrap_err();
+  let error = ParserError::downcast(error).unwrap();
+  assert!(
+    matches!(error.kind(), ParserErrorKind::XmlSyntax { message, .. } if message == "Unexpected end of stream")
+  );
+  assert_eq!(error.line(), Some(3));
+  assert_eq!(error.context(), Some("entity type Tests"));
+  assert_eq!(
+    error.with_source(Some("metadata.xml"), "<Edmx>\n<Schema>\n  <EntityType Name=\"Tests\">").to_string(),
+    "Metadata Parser Error, reason: Invalid XML, Unexpected end of stream\n --> metadata.xml:3:1\n  |\n3 | <EntityType Name=\"Tests\">\n  | ^\n  = note: in entity type Tests"
+  );
+}
+
+#[test]
+fn with_lenient_parser_it_collects_warnings_and_falls_back() {
+  let tags = vec![
+    open_tag!(Tag::Schema, ("Namespace", "test")),
+    open_tag!(Tag::EntityType,),
+    open_tag!(Tag::Property, ("Name", "Skipped"), ("Type", "Edm.String")),
+    close_tag!(Tag::Property),
+    close_tag!(Tag::EntityType),
+    open_tag!(Tag::EntityType, ("Name", "Stores")),
+    open_tag!(Tag::PropertyRef, ("Name", "ID")),
+    close_tag!(Tag::PropertyRef),
+    open_tag!(Tag::PropertyRef, ("Name", "Missing")),
+    close_tag!(Tag::PropertyRef),
+    open_tag!(Tag::Property, ("Name", "ID"), ("Type", "Edm.Int32")),
+    close_tag!(Tag::Property),
+    open_tag!(Tag::Property, ("Type", "Edm.String")),
+    close_tag!(Tag::Property),
+    open_tag!(
+      Tag::Property,
+      ("Name", "Area"),
+      ("Type", "Edm.GeographyPolygon")
+    ),
+    close_tag!(Tag::Property),
+    open_tag!(Tag::Property, ("Name", "Revenue"), ("Type", "Edm.Decimal")),
+    close_tag!(Tag::Property),
+    open_tag!(Tag::Property, ("Name", "Address"), ("Type", "test.Address")),
+    close_tag!(Tag::Property),
+    close_tag!(Tag::EntityType),
+    close_tag!(Tag::Schema),
+  ];
+  let tag_events: Vec<Result<TagEvent, ParserError>> = tags.into_iter().map(Ok).collect();
+  let mut parser = build_parser(tag_events).with_lenient(true);
+  let model = parser.parse().unwrap();
+
+  let warnings = parser.take_warnings();
+  assert_eq!(warnings.len(), 6);
+  assert!(matches!(
+    warnings[0].kind(),
+    ParserErrorKind::MissingAttribute { element, attribute } if element == "EntityType" && attribute == "Name"
+  ));
+  assert_eq!(warnings[0].context(), None);
+  assert!(matches!(
+    warnings[1].kind(),
+    ParserErrorKind::MissingAttribute { element, attribute } if element == "Property" && attribute == "Name"
+  ));
+  assert_eq!(warnings[1].context(), Some("entity type Stores"));
+  assert!(matches!(
+    warnings[2].kind(),
+    ParserErrorKind::UnknownType { name } if name == "Edm.GeographyPolygon"
+  ));
+  assert_eq!(
+    warnings[2].context(),
+    Some("entity type Stores, property Area")
+  );
+  assert!(matches!(
+    warnings[3].kind(),
+    ParserErrorKind::MissingAttribute { element, attribute } if element == "Property" && attribute == "Precision"
+  ));
+  assert!(matches!(
+    warnings[4].kind(),
+    ParserErrorKind::UnknownKeyProperty { entity, key } if entity == "Stores" && key == "Missing"
+  ));
+  assert!(matches!(
+    warnings[5].kind(),
+    ParserErrorKind::UnknownType { name } if name == "test.Address"
+  ));
+  assert_eq!(
+    warnings[5].context(),
+    Some("entity type Stores, property Address")
+  );
+  assert_eq!(
+    CdlRenderer.render(&model).unwrap(),
+    "entity Stores {\n  key ID: Integer;\n  Area: LargeString @odata.Type: 'Edm.GeographyPolygon';\n  Revenue: Decimal;\n  Address: LargeString @odata.Type: 'test.Address';\n}\n"
+  );
+}
+
+#[test]
+fn with_inconsistent_model_it_reports_validation_issues() {
+  let tags = vec![
+    open_tag!(Tag::Schema, ("Namespace", "test")),
+    open_tag!(Tag::EntityType, ("Name", "Orders")),
+    open_tag!(Tag::PropertyRef, ("Name", "ID")),
+    close_tag!(Tag::PropertyRef),
+    open_tag!(Tag::Property, ("Name", "ID"), ("Type", "Edm.Int32")),
+    close_tag!(Tag::Property),
+    open_tag!(
+      Tag::Property,
+      ("Name", "Note"),
+      ("Type", "Edm.Boolean"),
+      ("Scale", "2")
+    ),
+    close_tag!(Tag::Property),
+    open_tag!(Tag::Property, ("Name", "Note"), ("Type", "Edm.String")),
+    close_tag!(Tag::Property),
+    open_tag!(
+      Tag::NavigationProperty,
+      ("Name", "Customer"),
+      ("Type", "test.Customers")
+    ),
+    open_tag!(
+      Tag::ReferentialConstraint,
+      ("Property", "CustomerID"),
+      ("ReferencedProperty", "ID")
+    ),
+    close_tag!(Tag::ReferentialConstraint),
+    close_tag!(Tag::NavigationProperty),
+    open_tag!(
+      Tag::NavigationProperty,
+      ("Name", "Invoice"),
+      ("Type", "test.Invoices")
+    ),
+    close_tag!(Tag::NavigationProperty),
+    close_tag!(Tag::EntityType),
+    open_tag!(Tag::EntityType, ("Name", "Customers")),
+    open_tag!(Tag::PropertyRef, ("Name", "ID")),
+    close_tag!(Tag::PropertyRef),
+    open_tag!(
+      Tag::Property,
+      ("Name", "ID"),
+      ("Type", "Edm.Int32"),
+      ("Nullable", "false")
+    ),
+    close_tag!(Tag::Property),
+    close_tag!(Tag::EntityType),
+    open_tag!(Tag::EntityContainer, ("Name", "Container")),
+    open_tag!(
+      Tag::EntitySet,
+      ("Name", "Customers"),
+      ("EntityType", "test.Customer")
+    ),
+    close_tag!(Tag::EntitySet),
+    close_tag!(Tag::EntityContainer),
+    close_tag!(Tag::Schema),
+  ];
+  let model = parse_model(tags).unwrap();
+
+  let issues = validate(&model);
+  assert_eq!(
+    issues,
+    [
+      ValidationIssue::DuplicateElement {
+        owner: "entity type test.Orders".to_owned(),
+        name: "Note".to_owned(),
+      },
+      ValidationIssue::InvalidFacet {
+        owner: "entity type test.Orders".to_owned(),
+        property: "Note".to_owned(),
+        message: "Scale is not allowed on Edm.Boolean".to_owned(),
+      },
+      ValidationIssue::NullableKey {
+        entity: "test.Orders".to_owned(),
+        key: "ID".to_owned(),
+      },
+      ValidationIssue::UnknownConstraintProperty {
+        constraint: "navigation property test.Orders.Customer".to_owned(),
+        entity: "test.Orders".to_owned(),
+        property: "CustomerID".to_owned(),
+      },
+      ValidationIssue::UnknownNavigationTarget {
+        entity: "test.Orders".to_owned(),
+        navigation: "Invoice".to_owned(),
+        target: "entity type test.Invoices".to_owned(),
+      },
+      ValidationIssue::UnknownEntitySetType {
+        entity_set: "Customers".to_owned(),
+        entity_type: "test.Customer".to_owned(),
+      },
+    ]
+  );
+  assert_eq!(issues[2].severity(), Severity::Warning);
+  assert_eq!(issues[5].severity(), Severity::Error);
+}
+
+#[test]
+fn with_changed_facets_it_classifies_breaking_changes() {
+  let entity = |max_length: &str, scale: &str| {
+    vec![
+      open_tag!(Tag::Schema, ("Namespace", "test")),
+      open_tag!(Tag::EntityType, ("Name", "Items")),
+      open_tag!(
+        Tag::Property,
+        ("Name", "Code"),
+        ("Type", "Edm.String"),
+        ("MaxLength", max_length)
+      ),
+      close_tag!(Tag::Property),
+      open_tag!(
+        Tag::Property,
+        ("Name", "Amount"),
+        ("Type", "Edm.Decimal"),
+        ("Precision", "10"),
+        ("Scale", scale)
+      ),
+      close_tag!(Tag::Property),
+      close_tag!(Tag::EntityType),
+      close_tag!(Tag::Schema),
+    ]
+  };
+  let old = parse_model(entity("20", "2")).unwrap();
+  let new = parse_model(entity("10", "variable")).unwrap();
+
+  let changes = diff(&old, &new);
+  assert_eq!(
+    changes,
+    [
+      Change::MaxLengthChanged {
+        owner: "entity type test.Items".to_owned(),
+        property: "Code".to_owned(),
+        old: Some(20),
+        new: Some(10),
+      },
+      Change::ScaleChanged {
+        owner: "entity type test.Items".to_owned(),
+        property: "Amount".to_owned(),
+        old: Some(Scale::Fixed(2)),
+        new: Some(Scale::Variable),
+      },
+    ]
+  );
+  assert!(changes[0].is_breaking());
+  assert!(!changes[1].is_breaking());
+  let changes = diff(&new, &old);
+  assert!(!changes[0].is_breaking());
+  assert!(changes[1].is_breaking());
+}
+
 fn parse(tag_events: Vec<TagEvent>) -> Result<String, Box<dyn Error>> {
-  let tag_events: Vec<Result<TagEvent, TagError>> = tag_events.into_iter().map(Ok).collect();
+  let model = parse_model(tag_events)?;
+  CdlRenderer.render(&model)
+}
+
+fn parse_model(tag_events: Vec<TagEvent>) -> Result<Model, Box<dyn Error>> {
+  let tag_events: Vec<Result<TagEvent, ParserError>> = tag_events.into_iter().map(Ok).collect();
   let mut parser = build_parser(tag_events);
   parser.parse()
 }
 
-fn build_parser(events: Vec<Result<TagEvent, TagError>>) -> Parser {
+fn xml_syntax_error(position: Position) -> ParserError {
+  ParserError::new(ParserErrorKind::XmlSyntax {
+    message: "Unexpected end of stream".to_owned(),
+    source: None,
+  })
+  .with_position(Some(position))
+}
+
+fn build_parser(events: Vec<Result<TagEvent, ParserError>>) -> Parser {
   let tag_parser = VecTagParser::new(events);
   Parser::new(Box::new(tag_parser))
 }
 
 struct VecTagParser {
-  events: std::vec::IntoIter<Result<TagEvent, TagError>>,
+  events: std::vec::IntoIter<Result<TagEvent, ParserError>>,
 }
 
 impl VecTagParser {
-  pub fn new(events: Vec<Result<TagEvent, TagError>>) -> Self {
+  pub fn new(events: Vec<Result<TagEvent, ParserError>>) -> Self {
     let events = events.into_iter();
     VecTagParser { events }
   }
 }
 
 impl Iterator for VecTagParser {
-  type Item = Result<TagEvent, TagError>;
+  type Item = Result<TagEvent, ParserError>;
 
-  fn next(&mut self) -> Option<Result<TagEvent, TagError>> {
+  fn next(&mut self) -> Option<Result<TagEvent, ParserError>> {
     self.events.next()
   }
 }
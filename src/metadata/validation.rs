@@ -0,0 +1,431 @@
+use super::model::{EdmType, EntityType, Model, Property, PropertyType, Scale, Schema};
+use std::collections::HashSet;
+use std::error;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+  Error,
+  Warning,
+}
+
+impl fmt::Display for Severity {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Severity::Error => write!(formatter, "error"),
+      Severity::Warning => write!(formatter, "warning"),
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationIssue {
+  DuplicateType {
+    schema: String,
+    name: String,
+  },
+  DuplicateEntitySet {
+    schema: String,
+    name: String,
+  },
+  DuplicateElement {
+    owner: String,
+    name: String,
+  },
+  UnknownNavigationTarget {
+    entity: String,
+    navigation: String,
+    target: String,
+  },
+  UnknownEntitySetType {
+    entity_set: String,
+    entity_type: String,
+  },
+  NullableKey {
+    entity: String,
+    key: String,
+  },
+  UnknownConstraintRole {
+    association: String,
+    role: String,
+  },
+  UnknownConstraintProperty {
+    constraint: String,
+    entity: String,
+    property: String,
+  },
+  InvalidFacet {
+    owner: String,
+    property: String,
+    message: String,
+  },
+}
+
+impl ValidationIssue {
+  pub fn severity(&self) -> Severity {
+    match self {
+      ValidationIssue::NullableKey { .. } | ValidationIssue::InvalidFacet { .. } => {
+        Severity::Warning
+      }
+      _ => Severity::Error,
+    }
+  }
+}
+
+impl fmt::Display for ValidationIssue {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ValidationIssue::DuplicateType { schema, name } => {
+        write!(
+          formatter,
+          "Type {} is declared twice in schema {}",
+          name, schema
+        )
+      }
+      ValidationIssue::DuplicateEntitySet { schema, name } => write!(
+        formatter,
+        "Entity set {} is declared twice in schema {}",
+        name, schema
+      ),
+      ValidationIssue::DuplicateElement { owner, name } => {
+        write!(formatter, "Element {} is declared twice in {}", name, owner)
+      }
+      ValidationIssue::UnknownNavigationTarget {
+        entity,
+        navigation,
+        target,
+      } => write!(
+        formatter,
+        "Navigation property {} of entity type {} targets unknown {}",
+        navigation, entity, target
+      ),
+      ValidationIssue::UnknownEntitySetType {
+        entity_set,
+        entity_type,
+      } => write!(
+        formatter,
+        "Entity set {} references unknown entity type {}",
+        entity_set, entity_type
+      ),
+      ValidationIssue::NullableKey { entity, key } => write!(
+        formatter,
+        "Key property {} of entity type {} is nullable",
+        key, entity
+      ),
+      ValidationIssue::UnknownConstraintRole { association, role } => write!(
+        formatter,
+        "Referential constraint of association {} references unknown role {}",
+        association, role
+      ),
+      ValidationIssue::UnknownConstraintProperty {
+        constraint,
+        entity,
+        property,
+      } => write!(
+        formatter,
+        "Referential constraint of {} references unknown property {} of entity type {}",
+        constraint, property, entity
+      ),
+      ValidationIssue::InvalidFacet {
+        owner,
+        property,
+        message,
+      } => write!(
+        formatter,
+        "Invalid facet of property {} in {}, {}",
+        property, owner, message
+      ),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct ValidationError {
+  errors: usize,
+  warnings: usize,
+}
+
+impl ValidationError {
+  pub fn new(errors: usize, warnings: usize) -> Self {
+    ValidationError { errors, warnings }
+  }
+
+  pub fn errors(&self) -> usize {
+    self.errors
+  }
+
+  pub fn warnings(&self) -> usize {
+    self.warnings
+  }
+}
+
+impl fmt::Display for ValidationError {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      formatter,
+      "Metadata check failed, {} error(s) and {} warning(s) found",
+      self.errors, self.warnings
+    )
+  }
+}
+
+impl error::Error for ValidationError {}
+
+pub fn validate(model: &Model) -> Vec<ValidationIssue> {
+  let mut issues = Vec::new();
+  for schema in model.schemas() {
+    validate_names(schema, &mut issues);
+    for entity_type in schema.entity_types() {
+      validate_entity_type(model, schema, entity_type, &mut issues);
+    }
+    for complex_type in schema.complex_types() {
+      let owner = format!("complex type {}", schema.qualify(complex_type.name()));
+      let names = complex_type
+        .properties()
+        .iter()
+        .map(|property| property.name());
+      validate_element_names(&owner, names, &mut issues);
+      for property in complex_type.properties() {
+        validate_facets(&owner, property, &mut issues);
+      }
+    }
+    for entity_set in schema.entity_sets() {
+      let resolved = model
+        .resolve_entity_type(entity_set.entity_type())
+        .is_some()
+        || schema.entity_type(entity_set.entity_type()).is_some();
+      if !resolved {
+        issues.push(ValidationIssue::UnknownEntitySetType {
+          entity_set: entity_set.name().to_owned(),
+          entity_type: entity_set.entity_type().to_owned(),
+        });
+      }
+    }
+    validate_associations(model, schema, &mut issues);
+  }
+  issues
+}
+
+fn validate_names(schema: &Schema, issues: &mut Vec<ValidationIssue>) {
+  let mut types = HashSet::new();
+  let names = schema
+    .entity_types()
+    .iter()
+    .map(|entity_type| entity_type.name())
+    .chain(schema.complex_types().iter().map(|complex| complex.name()))
+    .chain(schema.enum_types().iter().map(|enum_type| enum_type.name()));
+  for name in names {
+    if !types.insert(name) {
+      issues.push(ValidationIssue::DuplicateType {
+        schema: schema.namespace().to_owned(),
+        name: name.to_owned(),
+      });
+    }
+  }
+  let mut entity_sets = HashSet::new();
+  for entity_set in schema.entity_sets() {
+    if !entity_sets.insert(entity_set.name()) {
+      issues.push(ValidationIssue::DuplicateEntitySet {
+        schema: schema.namespace().to_owned(),
+        name: entity_set.name().to_owned(),
+      });
+    }
+  }
+}
+
+fn validate_element_names<'a>(
+  owner: &str,
+  names: impl Iterator<Item = &'a str>,
+  issues: &mut Vec<ValidationIssue>,
+) {
+  let mut seen = HashSet::new();
+  for name in names {
+    if !seen.insert(name) {
+      issues.push(ValidationIssue::DuplicateElement {
+        owner: owner.to_owned(),
+        name: name.to_owned(),
+      });
+    }
+  }
+}
+
+fn validate_entity_type(
+  model: &Model,
+  schema: &Schema,
+  entity_type: &EntityType,
+  issues: &mut Vec<ValidationIssue>,
+) {
+  let entity = schema.qualify(entity_type.name());
+  let owner = format!("entity type {}", entity);
+  let names = entity_type.elements().iter().map(|element| element.name());
+  validate_element_names(&owner, names, issues);
+  for property in entity_type.properties() {
+    validate_facets(&owner, property, issues);
+  }
+  for key in entity_type.keys() {
+    let property = model
+      .entity_type_hierarchy(schema, entity_type)
+      .into_iter()
+      .find_map(|(_, entity_type)| entity_type.property(key));
+    if property.map(|property| property.facets().nullable()) == Some(true) {
+      issues.push(ValidationIssue::NullableKey {
+        entity: entity.clone(),
+        key: key.to_owned(),
+      });
+    }
+  }
+  for navigation in entity_type.navigation_properties() {
+    let target = model.resolve_navigation(schema, navigation);
+    if target.is_none() {
+      let target = match navigation.relationship() {
+        Some(relationship) => format!("association {}", relationship),
+        None if navigation.target().contains('.') => {
+          format!("entity type {}", navigation.target())
+        }
+        None => format!("entity type {}", schema.qualify(navigation.target())),
+      };
+      issues.push(ValidationIssue::UnknownNavigationTarget {
+        entity: entity.clone(),
+        navigation: navigation.name().to_owned(),
+        target,
+      });
+    }
+    let constraint = format!("navigation property {}.{}", entity, navigation.name());
+    for referential_constraint in navigation.referential_constraints() {
+      if !has_property(
+        model,
+        schema,
+        entity_type,
+        referential_constraint.property(),
+      ) {
+        issues.push(ValidationIssue::UnknownConstraintProperty {
+          constraint: constraint.clone(),
+          entity: entity.clone(),
+          property: referential_constraint.property().to_owned(),
+        });
+      }
+      if let Some(target) = target.as_ref() {
+        let referenced_property = referential_constraint.referenced_property();
+        if !has_property(
+          model,
+          target.schema(),
+          target.entity_type(),
+          referenced_property,
+        ) {
+          issues.push(ValidationIssue::UnknownConstraintProperty {
+            constraint: constraint.clone(),
+            entity: target.schema().qualify(target.entity_type().name()),
+            property: referenced_property.to_owned(),
+          });
+        }
+      }
+    }
+  }
+}
+
+fn validate_associations(model: &Model, schema: &Schema, issues: &mut Vec<ValidationIssue>) {
+  for association in schema.associations() {
+    let constraint = match association.constraint() {
+      Some(constraint) => constraint,
+      None => continue,
+    };
+    let name = schema.qualify(association.name());
+    let mut end_type = |role: &str| {
+      let end = association.end(role);
+      if end.is_none() {
+        issues.push(ValidationIssue::UnknownConstraintRole {
+          association: name.clone(),
+          role: role.to_owned(),
+        });
+      }
+      end.and_then(|end| {
+        model.find_entity_type(end.entity_type()).or_else(|| {
+          let name = end.entity_type().rsplit('.').next()?;
+          schema
+            .entity_type(name)
+            .map(|entity_type| (schema, entity_type))
+        })
+      })
+    };
+    let dependent = end_type(constraint.dependent_role());
+    let principal = end_type(constraint.principal_role());
+    let constraint_name = format!("association {}", name);
+    for referential_constraint in constraint.constraints() {
+      let ends = [
+        (dependent, referential_constraint.property()),
+        (principal, referential_constraint.referenced_property()),
+      ];
+      for (end, property) in ends {
+        if let Some((end_schema, entity_type)) = end {
+          if !has_property(model, end_schema, entity_type, property) {
+            issues.push(ValidationIssue::UnknownConstraintProperty {
+              constraint: constraint_name.clone(),
+              entity: end_schema.qualify(entity_type.name()),
+              property: property.to_owned(),
+            });
+          }
+        }
+      }
+    }
+  }
+}
+
+fn has_property(model: &Model, schema: &Schema, entity_type: &EntityType, name: &str) -> bool {
+  model
+    .entity_type_hierarchy(schema, entity_type)
+    .into_iter()
+    .any(|(_, entity_type)| entity_type.property(name).is_some())
+}
+
+fn validate_facets(owner: &str, property: &Property, issues: &mut Vec<ValidationIssue>) {
+  let facets = property.facets();
+  let edm_type = match property.property_type() {
+    PropertyType::Edm(edm_type) if property.original_type().is_none() => Some(*edm_type),
+    _ => None,
+  };
+  let mut invalid = |message: String| {
+    issues.push(ValidationIssue::InvalidFacet {
+      owner: owner.to_owned(),
+      property: property.name().to_owned(),
+      message,
+    })
+  };
+  let type_name = edm_type
+    .map(|edm_type| edm_type.name().to_owned())
+    .unwrap_or_else(|| "a non primitive type".to_owned());
+  if let Some(max_length) = facets.max_length() {
+    if !matches!(
+      edm_type,
+      Some(EdmType::String) | Some(EdmType::Binary) | Some(EdmType::Stream)
+    ) {
+      invalid(format!("MaxLength is not allowed on {}", type_name));
+    } else if max_length == 0 {
+      invalid("MaxLength must be positive".to_owned());
+    }
+  }
+  if let Some(precision) = facets.precision() {
+    match edm_type {
+      Some(EdmType::Decimal) if precision == 0 => {
+        invalid("Precision must be positive".to_owned());
+      }
+      Some(EdmType::Decimal)
+      | Some(EdmType::DateTime)
+      | Some(EdmType::DateTimeOffset)
+      | Some(EdmType::Time)
+      | Some(EdmType::TimeOfDay) => (),
+      _ => invalid(format!("Precision is not allowed on {}", type_name)),
+    }
+  }
+  if let Some(scale) = facets.scale() {
+    if edm_type != Some(EdmType::Decimal) {
+      invalid(format!("Scale is not allowed on {}", type_name));
+    } else if let (Scale::Fixed(scale), Some(precision)) = (scale, facets.precision()) {
+      if scale > precision {
+        invalid(format!(
+          "Scale {} is greater than Precision {}",
+          scale, precision
+        ));
+      }
+    }
+  }
+}
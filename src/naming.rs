@@ -0,0 +1,231 @@
+use super::metadata::model::{Element, Model};
+use clap::ValueEnum;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementCase {
+  Camel,
+  Pascal,
+  Snake,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Naming {
+  strip_prefixes: Vec<String>,
+  strip_suffixes: Vec<String>,
+  pluralize: bool,
+  element_case: Option<ElementCase>,
+}
+
+impl Naming {
+  pub fn with_strip_prefix(mut self, prefix: &str) -> Self {
+    self.strip_prefixes.push(prefix.to_owned());
+    self
+  }
+
+  pub fn with_strip_suffix(mut self, suffix: &str) -> Self {
+    self.strip_suffixes.push(suffix.to_owned());
+    self
+  }
+
+  pub fn with_pluralize(mut self, pluralize: bool) -> Self {
+    self.pluralize = pluralize;
+    self
+  }
+
+  pub fn with_element_case(mut self, element_case: Option<ElementCase>) -> Self {
+    self.element_case = element_case;
+    self
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.strip_prefixes.is_empty()
+      && self.strip_suffixes.is_empty()
+      && !self.pluralize
+      && self.element_case.is_none()
+  }
+
+  pub fn apply(&self, model: &mut Model) -> Result<(), Box<dyn Error>> {
+    if self.is_empty() {
+      return Ok(());
+    }
+    let mut entity_names = HashMap::new();
+    for schema in model.schemas_mut() {
+      let namespace = schema.namespace().to_owned();
+      for entity_type in schema.entity_types_mut() {
+        let owner = format!("{}.{}", namespace, entity_type.name());
+        let entity_name = self.entity_name(entity_type.name());
+        if let Some(other) =
+          entity_names.insert(format!("{}.{}", namespace, entity_name), owner.clone())
+        {
+          return Err(clashing_names("entity types", &other, &owner, &entity_name));
+        }
+        if entity_name != entity_type.name() {
+          entity_type.set_cds_name(&entity_name);
+        }
+        let mut element_names = HashMap::new();
+        for element in entity_type.elements_mut() {
+          let element_name = self.element_name(element.name());
+          if let Some(other) = element_names.insert(element_name.clone(), element.name().to_owned())
+          {
+            return Err(clashing_names(
+              &format!("elements of entity type {}", owner),
+              &other,
+              element.name(),
+              &element_name,
+            ));
+          }
+          if element_name != element.name() {
+            match element {
+              Element::Property(property) => property.set_cds_name(&element_name),
+              Element::NavigationProperty(navigation) => navigation.set_cds_name(&element_name),
+            }
+          }
+        }
+      }
+      for complex_type in schema.complex_types_mut() {
+        let owner = format!("{}.{}", namespace, complex_type.name());
+        let mut element_names = HashMap::new();
+        for property in complex_type.properties_mut() {
+          let element_name = self.element_name(property.name());
+          if let Some(other) =
+            element_names.insert(element_name.clone(), property.name().to_owned())
+          {
+            return Err(clashing_names(
+              &format!("elements of complex type {}", owner),
+              &other,
+              property.name(),
+              &element_name,
+            ));
+          }
+          if element_name != property.name() {
+            property.set_cds_name(&element_name);
+          }
+        }
+      }
+    }
+    Ok(())
+  }
+
+  fn entity_name(&self, name: &str) -> String {
+    let mut entity_name = name;
+    for prefix in self.strip_prefixes.iter() {
+      entity_name = strip_affix(entity_name, entity_name.strip_prefix(prefix.as_str()));
+    }
+    for suffix in self.strip_suffixes.iter() {
+      entity_name = strip_affix(entity_name, entity_name.strip_suffix(suffix.as_str()));
+    }
+    if self.pluralize {
+      pluralize(entity_name)
+    } else {
+      entity_name.to_owned()
+    }
+  }
+
+  fn element_name(&self, name: &str) -> String {
+    match self.element_case {
+      Some(element_case) => convert_case(name, element_case),
+      None => name.to_owned(),
+    }
+  }
+}
+
+fn clashing_names(kind: &str, first: &str, second: &str, name: &str) -> Box<dyn Error> {
+  Box::new(io::Error::new(
+    io::ErrorKind::InvalidInput,
+    format!(
+      "Naming options map the {} {} and {} to the same name {}",
+      kind, first, second, name
+    ),
+  ))
+}
+
+fn strip_affix<'a>(name: &'a str, stripped: Option<&'a str>) -> &'a str {
+  match stripped {
+    Some(stripped) if !stripped.is_empty() => stripped,
+    _ => name,
+  }
+}
+
+fn pluralize(name: &str) -> String {
+  let lowercase = name.to_lowercase();
+  let mut chars = lowercase.chars().rev();
+  let (last, before_last) = (chars.next(), chars.next());
+  if ["ss", "us", "x", "z", "ch", "sh"]
+    .iter()
+    .any(|ending| lowercase.ends_with(ending))
+  {
+    format!("{}es", name)
+  } else if last == Some('s') {
+    name.to_owned()
+  } else if last == Some('y') && before_last.is_some_and(|c| !"aeiou".contains(c)) {
+    format!("{}ies", &name[..name.len() - 1])
+  } else {
+    format!("{}s", name)
+  }
+}
+
+fn convert_case(name: &str, element_case: ElementCase) -> String {
+  let words = split_words(name);
+  match element_case {
+    ElementCase::Snake => {
+      let words: Vec<_> = words.iter().map(|word| word.to_lowercase()).collect();
+      words.join("_")
+    }
+    ElementCase::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+    ElementCase::Camel => words
+      .iter()
+      .enumerate()
+      .map(|(index, word)| {
+        if index == 0 {
+          word.to_lowercase()
+        } else {
+          capitalize(word)
+        }
+      })
+      .collect(),
+  }
+}
+
+fn split_words(name: &str) -> Vec<String> {
+  let chars: Vec<char> = name.chars().collect();
+  let mut words = Vec::new();
+  let mut word = String::new();
+  for (index, current) in chars.iter().enumerate() {
+    if *current == '_' || *current == '-' {
+      if !word.is_empty() {
+        words.push(std::mem::take(&mut word));
+      }
+      continue;
+    }
+    let previous = index.checked_sub(1).map(|index| chars[index]);
+    let next = chars.get(index + 1);
+    let boundary = current.is_uppercase()
+      && previous.is_some_and(|previous| {
+        previous.is_lowercase()
+          || previous.is_ascii_digit()
+          || (previous.is_uppercase() && next.is_some_and(|next| next.is_lowercase()))
+      });
+    if boundary && !word.is_empty() {
+      words.push(std::mem::take(&mut word));
+    }
+    word.push(*current);
+  }
+  if !word.is_empty() {
+    words.push(word);
+  }
+  words
+}
+
+fn capitalize(word: &str) -> String {
+  let mut chars = word.chars();
+  match chars.next() {
+    Some(first) => first
+      .to_uppercase()
+      .chain(chars.flat_map(char::to_lowercase))
+      .collect(),
+    None => String::new(),
+  }
+}
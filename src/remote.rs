@@ -0,0 +1,101 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::error::Error;
+use std::io::{self, Read};
+
+#[derive(Clone, Debug, Default)]
+pub struct MetadataRequest {
+  url: String,
+  credentials: Option<(String, String)>,
+  token: Option<String>,
+  headers: Vec<(String, String)>,
+  sap_client: Option<String>,
+}
+
+impl MetadataRequest {
+  pub fn new(url: &str) -> Self {
+    Self {
+      url: url.to_owned(),
+      ..Default::default()
+    }
+  }
+
+  pub fn with_basic_auth(mut self, user: &str, password: &str) -> Self {
+    self.credentials = Some((user.to_owned(), password.to_owned()));
+    self
+  }
+
+  pub fn with_token(mut self, token: &str) -> Self {
+    self.token = Some(token.to_owned());
+    self
+  }
+
+  pub fn with_header(mut self, name: &str, value: &str) -> Self {
+    self.headers.push((name.to_owned(), value.to_owned()));
+    self
+  }
+
+  pub fn with_sap_client(mut self, sap_client: &str) -> Self {
+    self.sap_client = Some(sap_client.to_owned());
+    self
+  }
+
+  pub fn url(&self) -> &str {
+    &self.url
+  }
+
+  pub fn metadata_url(&self) -> String {
+    let (path, query) = self
+      .url
+      .split_at(self.url.find(['?', '#']).unwrap_or(self.url.len()));
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix("/$metadata").unwrap_or(path);
+    format!("{}/$metadata{}", path, query)
+  }
+
+  pub fn service_name(&self) -> String {
+    let url = self.metadata_url();
+    let path = url.split(['?', '#']).next().unwrap_or(&url);
+    path
+      .trim_end_matches("/$metadata")
+      .rsplit('/')
+      .next()
+      .filter(|name| !name.is_empty())
+      .unwrap_or("service")
+      .to_owned()
+  }
+
+  pub fn fetch(&self) -> Result<Box<dyn Read>, Box<dyn Error>> {
+    let url = self.metadata_url();
+    let agent = ureq::AgentBuilder::new()
+      .redirect_auth_headers(ureq::RedirectAuthHeaders::SameHost)
+      .build();
+    let mut request = agent.get(&url).set("Accept", "application/xml");
+    if let Some((user, password)) = self.credentials.as_ref() {
+      let credentials = STANDARD.encode(format!("{}:{}", user, password));
+      request = request.set("Authorization", &format!("Basic {}", credentials));
+    }
+    if let Some(token) = self.token.as_ref() {
+      request = request.set("Authorization", &format!("Bearer {}", token));
+    }
+    for (name, value) in self.headers.iter() {
+      request = request.set(name, value);
+    }
+    if let Some(sap_client) = self.sap_client.as_ref() {
+      request = request.query("sap-client", sap_client);
+    }
+    match request.call() {
+      Ok(response) => Ok(response.into_reader()),
+      Err(ureq::Error::Status(status, response)) => Err(Box::new(io::Error::other(format!(
+        "Failed to fetch {}: HTTP {} {}",
+        url,
+        status,
+        response.status_text()
+      )))),
+      Err(error) => Err(Box::new(io::Error::other(format!(
+        "Failed to fetch {}",
+        error
+      )))),
+    }
+  }
+}
@@ -0,0 +1,342 @@
+use super::super::metadata::model::{
+  CdsType, CdsValue, ComplexType, Element, EntityType, EnumType, Facets, Model, NavigationProperty,
+  Property, Schema, ValueList, ValueListParameter,
+};
+use super::cds::{self, element_names, entity_name, ElementType};
+use super::Renderer;
+use std::collections::HashMap;
+use std::error::Error;
+
+const RESERVED_WORDS: [&str; 40] = [
+  "all",
+  "annotate",
+  "any",
+  "as",
+  "aspect",
+  "association",
+  "by",
+  "case",
+  "cast",
+  "composition",
+  "context",
+  "define",
+  "distinct",
+  "entity",
+  "enum",
+  "exists",
+  "extend",
+  "extract",
+  "false",
+  "from",
+  "in",
+  "key",
+  "many",
+  "namespace",
+  "new",
+  "not",
+  "null",
+  "of",
+  "on",
+  "order",
+  "select",
+  "service",
+  "some",
+  "true",
+  "type",
+  "using",
+  "view",
+  "when",
+  "where",
+  "with",
+];
+
+pub struct CdlRenderer;
+
+impl Renderer for CdlRenderer {
+  fn render(&self, model: &Model) -> Result<String, Box<dyn Error>> {
+    let mut cds = String::from("");
+    for (_, enum_type) in model.enum_types() {
+      cds.push_str(&Self::render_enum_type(enum_type));
+    }
+    for (_, complex_type) in model.complex_types() {
+      cds.push_str(&Self::render_complex_type(complex_type));
+    }
+    for (schema, entity_type) in model.entity_types() {
+      cds.push_str(&Self::render_entity(model, schema, entity_type));
+    }
+    Ok(cds)
+  }
+}
+
+impl CdlRenderer {
+  fn render_enum_type(enum_type: &EnumType) -> String {
+    let underlying_type = Self::render_cds_type(&cds::edm_type_to_cds(
+      enum_type.underlying_type(),
+      &Facets::default(),
+    ));
+    let mut cds = format!(
+      "type {} : {} enum {{\n",
+      cds_identifier(enum_type.name()),
+      underlying_type
+    );
+    for member in enum_type.members() {
+      cds.push_str(&format!(
+        "  {} = {};\n",
+        cds_identifier(member.name()),
+        member.value()
+      ));
+    }
+    cds.push_str("}\n");
+    cds
+  }
+
+  fn render_complex_type(complex_type: &ComplexType) -> String {
+    let mut cds = format!("type {} {{\n", cds_identifier(complex_type.name()));
+    let names = element_names(complex_type.properties().iter());
+    for property in complex_type.properties() {
+      for line in Self::render_property(property, false, &names).lines() {
+        cds.push_str("  ");
+        cds.push_str(line);
+        cds.push('\n');
+      }
+    }
+    cds.push_str("}\n");
+    cds
+  }
+
+  fn render_entity(model: &Model, schema: &Schema, entity_type: &EntityType) -> String {
+    let mut cds = Self::render_original_name(entity_type.name(), entity_type.cds_name());
+    cds.push_str(&format!(
+      "entity {} {{\n",
+      cds_identifier(entity_name(entity_type))
+    ));
+    let names = element_names(entity_type.properties());
+    for element in entity_type.elements() {
+      let element_cds = match element {
+        Element::Property(property) => {
+          Self::render_property(property, entity_type.is_key(property.name()), &names)
+        }
+        Element::NavigationProperty(navigation) => {
+          Self::render_navigation(model, schema, navigation)
+        }
+      };
+      for line in element_cds.lines() {
+        cds.push_str("  ");
+        cds.push_str(line);
+        cds.push('\n');
+      }
+    }
+    cds.push_str("}\n");
+    cds
+  }
+
+  fn render_property(property: &Property, is_key: bool, names: &HashMap<&str, &str>) -> String {
+    let mut cds = String::new();
+    for value_list in property.value_lists() {
+      cds.push_str(&Self::render_value_list(value_list, names));
+    }
+    if property.value_list_with_fixed_values() {
+      cds.push_str("@Common.ValueListWithFixedValues\n");
+    }
+    cds.push_str(&Self::render_original_name(
+      property.name(),
+      property.cds_name(),
+    ));
+    if is_key {
+      cds.push_str("key ");
+    }
+    cds.push_str(&format!(
+      "{}: {};\n",
+      cds_identifier(property.cds_name().unwrap_or(property.name())),
+      Self::render_type(property)
+    ));
+    cds
+  }
+
+  fn render_navigation(model: &Model, schema: &Schema, navigation: &NavigationProperty) -> String {
+    let (target, _) = cds::association_target(model, schema, navigation);
+    let target = cds_identifier(target.rsplit('.').next().unwrap_or(&target));
+    let mut cds = Self::render_original_name(navigation.name(), navigation.cds_name());
+    cds.push_str(&format!(
+      "{}: Association to {} on ...;\n",
+      cds_identifier(navigation.cds_name().unwrap_or(navigation.name())),
+      target
+    ));
+    cds
+  }
+
+  fn render_original_name(name: &str, cds_name: Option<&str>) -> String {
+    match cds_name {
+      Some(_) => format!("@odata.name: {}\n", cds_string(name)),
+      None => String::new(),
+    }
+  }
+
+  fn render_type(property: &Property) -> String {
+    let type_name = match cds::property_type(property) {
+      ElementType::Cds(cds_type) => Self::render_cds_type(&cds_type),
+      ElementType::Named(name) => cds_identifier(name.rsplit('.').next().unwrap_or(name)),
+    };
+    if property.is_collection() {
+      format!("many {type_name}")
+    } else {
+      type_name
+    }
+  }
+
+  fn render_cds_type(cds_type: &CdsType) -> String {
+    let mut cds = cds_type.name().to_owned();
+    if !cds_type.arguments().is_empty() {
+      cds.push_str(&format!("({})", cds_type.arguments().join(", ")));
+    }
+    for annotation in cds_type.annotations() {
+      let value = match annotation.value() {
+        CdsValue::String(value) => cds_string(value),
+        CdsValue::Number(value) => value.to_owned(),
+        CdsValue::Boolean(value) => value.to_string(),
+        CdsValue::Symbol(value) => format!("#{}", value),
+      };
+      cds.push_str(&format!(" @{}: {}", annotation.term(), value));
+    }
+    cds
+  }
+
+  fn render_value_list(value_list: &ValueList, names: &HashMap<&str, &str>) -> String {
+    let mut cds = match value_list.qualifier() {
+      Some(qualifier) => format!("@Common.ValueList #{}: {{\n", qualifier),
+      None => String::from("@Common.ValueList: {\n"),
+    };
+    cds.push_str(&format!(
+      "  CollectionPath: {},\n",
+      cds_string(value_list.collection_path())
+    ));
+    if let Some(label) = value_list.label() {
+      cds.push_str(&format!("  Label: {},\n", cds_string(label)));
+    }
+    if let Some(search_supported) = value_list.search_supported() {
+      cds.push_str(&format!("  SearchSupported: {search_supported},\n"));
+    }
+    cds.push_str("  Parameters: [\n");
+    let parameters: Vec<_> = value_list
+      .parameters()
+      .iter()
+      .map(|parameter| Self::render_value_list_parameter(parameter, names))
+      .collect();
+    cds.push_str(&parameters.join(",\n"));
+    if !parameters.is_empty() {
+      cds.push('\n');
+    }
+    cds.push_str("  ]\n}\n");
+    cds
+  }
+
+  fn render_value_list_parameter(
+    parameter: &ValueListParameter,
+    names: &HashMap<&str, &str>,
+  ) -> String {
+    let mut properties = vec![format!("$Type: 'Common.{}'", parameter.kind())];
+    if let Some(local_data_property) = parameter.local_data_property() {
+      properties.push(format!(
+        "LocalDataProperty: {}",
+        cds_path(
+          names
+            .get(local_data_property)
+            .copied()
+            .unwrap_or(local_data_property)
+        )
+      ));
+    }
+    if let Some(value_list_property) = parameter.value_list_property() {
+      properties.push(format!(
+        "ValueListProperty: {}",
+        cds_string(value_list_property)
+      ));
+    }
+    if let Some(constant) = parameter.constant() {
+      properties.push(format!("Constant: {}", cds_string(constant)));
+    }
+    let properties: Vec<_> = properties.iter().map(|p| format!("      {p}")).collect();
+    format!("    {{\n{}\n    }}", properties.join(",\n"))
+  }
+}
+
+pub fn cds_identifier(name: &str) -> String {
+  if identifier_problem(name).is_some() {
+    format!("![{}]", name.replace(']', "]]"))
+  } else {
+    name.to_owned()
+  }
+}
+
+pub fn quoted_identifiers(model: &Model) -> Vec<String> {
+  let mut quoted = Vec::new();
+  let mut check = |name: &str, description: String| {
+    if let Some(problem) = identifier_problem(name) {
+      quoted.push(format!(
+        "{} is written as {}, {}",
+        description,
+        cds_identifier(name),
+        problem
+      ));
+    }
+  };
+  for (schema, enum_type) in model.enum_types() {
+    let owner = format!("enum type {}", schema.qualify(enum_type.name()));
+    check(enum_type.name(), owner.clone());
+    for member in enum_type.members() {
+      check(
+        member.name(),
+        format!("member {} of {}", member.name(), owner),
+      );
+    }
+  }
+  for (schema, complex_type) in model.complex_types() {
+    let owner = format!("complex type {}", schema.qualify(complex_type.name()));
+    check(complex_type.name(), owner.clone());
+    for property in complex_type.properties() {
+      check(
+        property.cds_name().unwrap_or(property.name()),
+        format!("property {} of {}", property.name(), owner),
+      );
+    }
+  }
+  for (schema, entity_type) in model.entity_types() {
+    let owner = format!("entity type {}", schema.qualify(entity_type.name()));
+    check(entity_name(entity_type), owner.clone());
+    for element in entity_type.elements() {
+      let kind = match element {
+        Element::Property(_) => "property",
+        Element::NavigationProperty(_) => "navigation property",
+      };
+      check(
+        element.cds_name().unwrap_or(element.name()),
+        format!("{} {} of {}", kind, element.name(), owner),
+      );
+    }
+  }
+  quoted
+}
+
+fn cds_string(value: &str) -> String {
+  format!("'{}'", value.replace('\'', "''"))
+}
+
+fn cds_path(path: &str) -> String {
+  let segments: Vec<_> = path.split('.').map(cds_identifier).collect();
+  segments.join(".")
+}
+
+fn identifier_problem(name: &str) -> Option<&'static str> {
+  let mut chars = name.chars();
+  let valid = chars
+    .next()
+    .is_some_and(|first| first.is_alphabetic() || first == '_' || first == '$')
+    && chars.all(|current| current.is_alphanumeric() || current == '_' || current == '$');
+  if !valid {
+    Some("it is not a valid CDS identifier")
+  } else if RESERVED_WORDS.contains(&name.to_lowercase().as_str()) {
+    Some("it is a reserved word in CDS")
+  } else {
+    None
+  }
+}
@@ -0,0 +1,120 @@
+use super::super::metadata::model::{
+  CdsType, CdsValue, EdmType, EntityType, Facets, Model, NavigationProperty, NavigationTarget,
+  Property, PropertyType, Schema,
+};
+use std::collections::HashMap;
+
+const CDS_TYPES: [&str; 21] = [
+  "UUID",
+  "Boolean",
+  "Integer",
+  "UInt8",
+  "Int16",
+  "Int32",
+  "Int64",
+  "Integer64",
+  "Decimal",
+  "DecimalFloat",
+  "Double",
+  "Date",
+  "Time",
+  "DateTime",
+  "Timestamp",
+  "String",
+  "LargeString",
+  "Binary",
+  "LargeBinary",
+  "Vector",
+  "Map",
+];
+
+pub fn is_builtin_type(name: &str) -> bool {
+  CDS_TYPES.contains(&name)
+}
+
+pub enum ElementType<'a> {
+  Cds(CdsType),
+  Named(&'a str),
+}
+
+pub fn property_type(property: &Property) -> ElementType<'_> {
+  if let Some(cds_type) = property.cds_type() {
+    return ElementType::Cds(cds_type.clone());
+  }
+  match (property.property_type(), property.original_type()) {
+    (_, Some(original_type)) => ElementType::Cds(
+      CdsType::new("LargeString")
+        .with_annotation("odata.Type", CdsValue::String(original_type.to_owned())),
+    ),
+    (PropertyType::Edm(edm_type), None) => {
+      ElementType::Cds(edm_type_to_cds(*edm_type, property.facets()))
+    }
+    (PropertyType::Complex(name) | PropertyType::Enum(name), None) => ElementType::Named(name),
+  }
+}
+
+pub fn edm_type_to_cds(edm_type: EdmType, facets: &Facets) -> CdsType {
+  let (name, odata_type) = match edm_type {
+    EdmType::Guid => ("UUID", None),
+    EdmType::Boolean => ("Boolean", None),
+    EdmType::Int16 | EdmType::Int32 => ("Integer", None),
+    EdmType::Int64 => ("Integer64", None),
+    EdmType::Decimal => ("Decimal", None),
+    EdmType::Double => ("Double", None),
+    EdmType::Date => ("Date", None),
+    EdmType::TimeOfDay | EdmType::Time => ("Time", None),
+    EdmType::DateTime | EdmType::DateTimeOffset => ("DateTime", None),
+    EdmType::String => ("String", None),
+    EdmType::Binary => ("Binary", None),
+    EdmType::Single => ("Double", Some("Edm.Single")),
+    EdmType::Byte => ("Integer", Some("Edm.Byte")),
+    EdmType::SByte => ("Integer", Some("Edm.SByte")),
+    EdmType::Stream => ("LargeBinary", Some("Edm.Stream")),
+  };
+  let cds_type = CdsType::new(name).with_arguments(facet_arguments(name, facets));
+  match odata_type {
+    Some(odata_type) => {
+      cds_type.with_annotation("odata.Type", CdsValue::String(odata_type.to_owned()))
+    }
+    None => cds_type,
+  }
+}
+
+pub fn facet_arguments(name: &str, facets: &Facets) -> Vec<String> {
+  match (name, facets.precision(), facets.scale()) {
+    ("String" | "Binary", _, _) => facets
+      .max_length()
+      .into_iter()
+      .map(|length| length.to_string())
+      .collect(),
+    ("Decimal", Some(precision), Some(scale)) => vec![precision.to_string(), scale.to_string()],
+    ("Decimal", Some(precision), None) => vec![precision.to_string()],
+    _ => Vec::new(),
+  }
+}
+
+pub fn association_target<'a>(
+  model: &'a Model,
+  schema: &'a Schema,
+  navigation: &NavigationProperty,
+) -> (String, Option<NavigationTarget<'a>>) {
+  let target = model.resolve_navigation(schema, navigation);
+  let name = match target {
+    Some(target) => target.schema().qualify(entity_name(target.entity_type())),
+    None if navigation.target().contains('.') => navigation.target().to_owned(),
+    None => schema.qualify(navigation.target()),
+  };
+  (name, target)
+}
+
+pub fn entity_name(entity_type: &EntityType) -> &str {
+  entity_type.cds_name().unwrap_or(entity_type.name())
+}
+
+pub fn element_names<'a>(
+  properties: impl Iterator<Item = &'a Property>,
+) -> HashMap<&'a str, &'a str> {
+  properties
+    .filter_map(|property| Some((property.name(), property.cds_name()?)))
+    .collect()
+}
@@ -0,0 +1,281 @@
+use super::super::metadata::model::{
+  CdsType, CdsValue, ComplexType, Element, EntityType, EnumType, Facets, Model, NavigationProperty,
+  Property, Schema, ValueList, ValueListParameter,
+};
+use super::cds::{self, element_names, entity_name, ElementType};
+use super::Renderer;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::error::Error;
+
+pub struct CsnRenderer;
+
+impl Renderer for CsnRenderer {
+  fn render(&self, model: &Model) -> Result<String, Box<dyn Error>> {
+    let mut definitions = Map::new();
+    for (schema, enum_type) in model.enum_types() {
+      definitions.insert(
+        schema.qualify(enum_type.name()),
+        Self::render_enum_type(enum_type),
+      );
+    }
+    for (schema, complex_type) in model.complex_types() {
+      definitions.insert(
+        schema.qualify(complex_type.name()),
+        Self::render_complex_type(complex_type),
+      );
+    }
+    for (schema, entity_type) in model.entity_types() {
+      definitions.insert(
+        schema.qualify(entity_name(entity_type)),
+        Self::render_entity(model, schema, entity_type),
+      );
+    }
+    let csn = json!({ "definitions": definitions });
+    let mut csn = serde_json::to_string_pretty(&csn)?;
+    csn.push('\n');
+    Ok(csn)
+  }
+}
+
+impl CsnRenderer {
+  fn render_enum_type(enum_type: &EnumType) -> Value {
+    let mut csn = Map::new();
+    csn.insert("kind".to_owned(), json!("type"));
+    csn.extend(Self::render_cds_type(&cds::edm_type_to_cds(
+      enum_type.underlying_type(),
+      &Facets::default(),
+    )));
+    let mut members = Map::new();
+    for member in enum_type.members() {
+      members.insert(member.name().to_owned(), json!({ "val": member.value() }));
+    }
+    csn.insert("enum".to_owned(), Value::Object(members));
+    Value::Object(csn)
+  }
+
+  fn render_complex_type(complex_type: &ComplexType) -> Value {
+    let mut elements = Map::new();
+    let names = element_names(complex_type.properties().iter());
+    for property in complex_type.properties() {
+      elements.insert(
+        property.cds_name().unwrap_or(property.name()).to_owned(),
+        Self::render_property(property, false, &names),
+      );
+    }
+    json!({
+      "kind": "type",
+      "elements": elements,
+    })
+  }
+
+  fn render_entity(model: &Model, schema: &Schema, entity_type: &EntityType) -> Value {
+    let mut elements = Map::new();
+    let names = element_names(entity_type.properties());
+    for element in entity_type.elements() {
+      let element_csn = match element {
+        Element::Property(property) => {
+          Self::render_property(property, entity_type.is_key(property.name()), &names)
+        }
+        Element::NavigationProperty(navigation) => {
+          Self::render_navigation(model, schema, navigation, &names)
+        }
+      };
+      elements.insert(
+        element.cds_name().unwrap_or(element.name()).to_owned(),
+        element_csn,
+      );
+    }
+    let mut csn = Map::new();
+    csn.insert("kind".to_owned(), json!("entity"));
+    if entity_type.cds_name().is_some() {
+      csn.insert("@odata.name".to_owned(), json!(entity_type.name()));
+    }
+    csn.insert("elements".to_owned(), Value::Object(elements));
+    Value::Object(csn)
+  }
+
+  fn render_navigation(
+    model: &Model,
+    schema: &Schema,
+    navigation: &NavigationProperty,
+    names: &HashMap<&str, &str>,
+  ) -> Value {
+    let (target_name, target) = cds::association_target(model, schema, navigation);
+    let target_type = target.map(|target| target.entity_type());
+    let mut csn = Map::new();
+    if navigation.cds_name().is_some() {
+      csn.insert("@odata.name".to_owned(), json!(navigation.name()));
+    }
+    csn.insert("type".to_owned(), json!("cds.Association"));
+    csn.insert("target".to_owned(), json!(target_name));
+    if target.map_or(navigation.is_collection(), |target| target.is_collection()) {
+      csn.insert("cardinality".to_owned(), json!({ "max": "*" }));
+    }
+    let association = navigation.cds_name().unwrap_or(navigation.name());
+    let mut on = Vec::new();
+    for constraint in model.navigation_constraints(navigation) {
+      if !on.is_empty() {
+        on.push(json!("and"));
+      }
+      let referenced_property = constraint.referenced_property();
+      let referenced_property = target_type
+        .and_then(|target_type| target_type.property(referenced_property))
+        .and_then(|property| property.cds_name())
+        .unwrap_or(referenced_property);
+      let property = names
+        .get(constraint.property())
+        .copied()
+        .unwrap_or(constraint.property());
+      on.push(json!({ "ref": [association, referenced_property] }));
+      on.push(json!("="));
+      on.push(json!({ "ref": [property] }));
+    }
+    if let (true, Some(partner)) = (on.is_empty(), navigation.partner()) {
+      let partner = target_type
+        .and_then(|target_type| {
+          target_type
+            .navigation_properties()
+            .find(|navigation| navigation.name() == partner)
+        })
+        .and_then(|navigation| navigation.cds_name())
+        .unwrap_or(partner);
+      on.push(json!({ "ref": [association, partner] }));
+      on.push(json!("="));
+      on.push(json!({ "ref": ["$self"] }));
+    }
+    if !on.is_empty() {
+      csn.insert("on".to_owned(), Value::Array(on));
+    }
+    Value::Object(csn)
+  }
+
+  fn render_property(property: &Property, is_key: bool, names: &HashMap<&str, &str>) -> Value {
+    let mut element = Map::new();
+    for value_list in property.value_lists() {
+      let term = match value_list.qualifier() {
+        Some(qualifier) => format!("@Common.ValueList#{}", qualifier),
+        None => "@Common.ValueList".to_owned(),
+      };
+      element.insert(term, Self::render_value_list(value_list, names));
+    }
+    if property.value_list_with_fixed_values() {
+      element.insert("@Common.ValueListWithFixedValues".to_owned(), json!(true));
+    }
+    if property.cds_name().is_some() {
+      element.insert("@odata.name".to_owned(), json!(property.name()));
+    }
+    if is_key {
+      element.insert("key".to_owned(), json!(true));
+    }
+    element.extend(Self::render_type(property));
+    Value::Object(element)
+  }
+
+  fn render_type(property: &Property) -> Map<String, Value> {
+    let csn = match cds::property_type(property) {
+      ElementType::Cds(cds_type) => Self::render_cds_type(&cds_type),
+      ElementType::Named(name) => {
+        let mut csn = Map::new();
+        csn.insert("type".to_owned(), json!(name));
+        csn
+      }
+    };
+    if property.is_collection() {
+      let (annotations, csn): (Map<_, _>, Map<_, _>) =
+        csn.into_iter().partition(|(key, _)| key.starts_with('@'));
+      let mut items = annotations;
+      items.insert("items".to_owned(), Value::Object(csn));
+      items
+    } else {
+      csn
+    }
+  }
+
+  fn render_cds_type(cds_type: &CdsType) -> Map<String, Value> {
+    let name = cds_type.name();
+    let mut csn = Map::new();
+    if cds::is_builtin_type(name) {
+      csn.insert("type".to_owned(), json!(format!("cds.{}", name)));
+    } else {
+      csn.insert("type".to_owned(), json!(name));
+    }
+    let facets: &[&str] = match name {
+      "Decimal" => &["precision", "scale"],
+      "String" | "Binary" | "LargeString" | "LargeBinary" | "Vector" => &["length"],
+      _ => &[],
+    };
+    for (facet, argument) in facets.iter().zip(cds_type.arguments()) {
+      csn.insert((*facet).to_owned(), csn_number(argument));
+    }
+    for annotation in cds_type.annotations() {
+      let value = match annotation.value() {
+        CdsValue::String(value) => json!(value),
+        CdsValue::Number(value) => value
+          .parse::<serde_json::Number>()
+          .map(Value::Number)
+          .unwrap_or_else(|_| json!(value)),
+        CdsValue::Boolean(value) => json!(value),
+        CdsValue::Symbol(value) => json!({ "#": value }),
+      };
+      csn.insert(format!("@{}", annotation.term()), value);
+    }
+    csn
+  }
+
+  fn render_value_list(value_list: &ValueList, names: &HashMap<&str, &str>) -> Value {
+    let mut csn = Map::new();
+    csn.insert(
+      "CollectionPath".to_owned(),
+      json!(value_list.collection_path()),
+    );
+    if let Some(label) = value_list.label() {
+      csn.insert("Label".to_owned(), json!(label));
+    }
+    if let Some(search_supported) = value_list.search_supported() {
+      csn.insert("SearchSupported".to_owned(), json!(search_supported));
+    }
+    let parameters: Vec<_> = value_list
+      .parameters()
+      .iter()
+      .map(|parameter| Self::render_value_list_parameter(parameter, names))
+      .collect();
+    csn.insert("Parameters".to_owned(), Value::Array(parameters));
+    Value::Object(csn)
+  }
+
+  fn render_value_list_parameter(
+    parameter: &ValueListParameter,
+    names: &HashMap<&str, &str>,
+  ) -> Value {
+    let mut csn = Map::new();
+    csn.insert(
+      "$Type".to_owned(),
+      json!(format!("Common.{}", parameter.kind())),
+    );
+    if let Some(local_data_property) = parameter.local_data_property() {
+      let local_data_property = names
+        .get(local_data_property)
+        .copied()
+        .unwrap_or(local_data_property);
+      csn.insert(
+        "LocalDataProperty".to_owned(),
+        json!({ "=": local_data_property }),
+      );
+    }
+    if let Some(value_list_property) = parameter.value_list_property() {
+      csn.insert("ValueListProperty".to_owned(), json!(value_list_property));
+    }
+    if let Some(constant) = parameter.constant() {
+      csn.insert("Constant".to_owned(), json!(constant));
+    }
+    Value::Object(csn)
+  }
+}
+
+fn csn_number(value: &str) -> Value {
+  value
+    .parse::<u64>()
+    .map(Value::from)
+    .unwrap_or_else(|_| Value::from(value))
+}
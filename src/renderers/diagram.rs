@@ -0,0 +1,210 @@
+use super::super::metadata::model::{EntityType, Model, Multiplicity, PropertyType, Schema};
+use super::Renderer;
+use std::collections::HashSet;
+use std::error::Error;
+
+pub struct MermaidRenderer;
+
+pub struct DotRenderer;
+
+struct Field {
+  name: String,
+  type_name: String,
+  is_key: bool,
+  is_foreign_key: bool,
+}
+
+struct Edge {
+  source: String,
+  target: String,
+  label: String,
+  multiplicity: Multiplicity,
+  source_multiplicity: Option<Multiplicity>,
+}
+
+impl Renderer for MermaidRenderer {
+  fn render(&self, model: &Model) -> Result<String, Box<dyn Error>> {
+    let mut mermaid = String::from("erDiagram\n");
+    for (schema, entity_type) in model.entity_types() {
+      mermaid.push_str(&format!(
+        "  {} {{\n",
+        Self::render_name(schema, entity_type)
+      ));
+      for field in fields(model, schema, entity_type) {
+        let mut keys = Vec::new();
+        if field.is_key {
+          keys.push("PK");
+        }
+        if field.is_foreign_key {
+          keys.push("FK");
+        }
+        mermaid.push_str(&format!(
+          "    {} {}{}\n",
+          field.type_name.replace('.', "_"),
+          field.name,
+          if keys.is_empty() {
+            String::new()
+          } else {
+            format!(" {}", keys.join(", "))
+          }
+        ));
+      }
+      mermaid.push_str("  }\n");
+    }
+    for edge in edges(model) {
+      let source_marker = match edge.source_multiplicity {
+        Some(Multiplicity::One) => "||",
+        Some(Multiplicity::ZeroOrOne) => "|o",
+        Some(Multiplicity::Many) | None => "}o",
+      };
+      let target_marker = match edge.multiplicity {
+        Multiplicity::One => "||",
+        Multiplicity::ZeroOrOne => "o|",
+        Multiplicity::Many => "o{",
+      };
+      mermaid.push_str(&format!(
+        "  {} {}--{} {} : \"{}\"\n",
+        edge.source.replace('.', "_"),
+        source_marker,
+        target_marker,
+        edge.target.replace('.', "_"),
+        edge.label
+      ));
+    }
+    Ok(mermaid)
+  }
+}
+
+impl MermaidRenderer {
+  fn render_name(schema: &Schema, entity_type: &EntityType) -> String {
+    node_name(schema, entity_type).replace('.', "_")
+  }
+}
+
+impl Renderer for DotRenderer {
+  fn render(&self, model: &Model) -> Result<String, Box<dyn Error>> {
+    let mut dot = String::from("digraph metadata {\n  rankdir=LR;\n  node [shape=record];\n");
+    for (schema, entity_type) in model.entity_types() {
+      let fields: Vec<_> = fields(model, schema, entity_type)
+        .iter()
+        .map(|field| {
+          let mut keys = Vec::new();
+          if field.is_key {
+            keys.push("PK");
+          }
+          if field.is_foreign_key {
+            keys.push("FK");
+          }
+          let keys = if keys.is_empty() {
+            String::new()
+          } else {
+            format!(" ({})", keys.join(", "))
+          };
+          format!(
+            "{}: {}{}\\l",
+            Self::escape_record(&field.name),
+            Self::escape_record(&field.type_name),
+            keys
+          )
+        })
+        .collect();
+      dot.push_str(&format!(
+        "  \"{}\" [label=\"{{{}|{}}}\"];\n",
+        node_name(schema, entity_type),
+        Self::escape_record(entity_type.name()),
+        fields.join("")
+      ));
+    }
+    for edge in edges(model) {
+      let cardinality = match edge.source_multiplicity {
+        Some(source_multiplicity) => format!("{}:{}", source_multiplicity, edge.multiplicity),
+        None => edge.multiplicity.to_string(),
+      };
+      dot.push_str(&format!(
+        "  \"{}\" -> \"{}\" [label=\"{} ({})\"];\n",
+        edge.source, edge.target, edge.label, cardinality
+      ));
+    }
+    dot.push_str("}\n");
+    Ok(dot)
+  }
+}
+
+impl DotRenderer {
+  fn escape_record(value: &str) -> String {
+    let mut escaped = String::new();
+    for c in value.chars() {
+      if matches!(c, '{' | '}' | '|' | '<' | '>' | '"' | '\\') {
+        escaped.push('\\');
+      }
+      escaped.push(c);
+    }
+    escaped
+  }
+}
+
+fn node_name(schema: &Schema, entity_type: &EntityType) -> String {
+  schema.qualify(entity_type.name())
+}
+
+fn fields(model: &Model, schema: &Schema, entity_type: &EntityType) -> Vec<Field> {
+  let foreign_keys: HashSet<String> = model
+    .foreign_keys(schema, entity_type)
+    .iter()
+    .flat_map(|foreign_key| foreign_key.constraints().to_vec())
+    .map(|constraint| constraint.property().to_owned())
+    .collect();
+  entity_type
+    .properties()
+    .map(|property| {
+      let type_name = match property.property_type() {
+        PropertyType::Edm(edm_type) => edm_type.name().trim_start_matches("Edm.").to_owned(),
+        PropertyType::Complex(name) | PropertyType::Enum(name) => {
+          name.rsplit('.').next().unwrap_or(name).to_owned()
+        }
+      };
+      Field {
+        name: property.name().to_owned(),
+        type_name: if property.is_collection() {
+          format!("{}[]", type_name)
+        } else {
+          type_name
+        },
+        is_key: entity_type.is_key(property.name()),
+        is_foreign_key: foreign_keys.contains(property.name()),
+      }
+    })
+    .collect()
+}
+
+fn edges(model: &Model) -> Vec<Edge> {
+  let mut edges = Vec::new();
+  let mut rendered = HashSet::new();
+  for (schema, entity_type) in model.entity_types() {
+    let source = node_name(schema, entity_type);
+    for navigation in entity_type.navigation_properties() {
+      let target = match model.resolve_navigation(schema, navigation) {
+        Some(target) => target,
+        None => continue,
+      };
+      let target_name = node_name(target.schema(), target.entity_type());
+      let pair = match (navigation.relationship(), navigation.partner()) {
+        (Some(relationship), _) => relationship.to_owned(),
+        (None, Some(partner)) => format!("{}/{}", target_name, partner),
+        (None, None) => format!("{}/{}", source, navigation.name()),
+      };
+      if !rendered.insert(pair) {
+        continue;
+      }
+      rendered.insert(format!("{}/{}", source, navigation.name()));
+      edges.push(Edge {
+        source: source.clone(),
+        target: target_name,
+        label: navigation.name().to_owned(),
+        multiplicity: target.multiplicity(),
+        source_multiplicity: target.source_multiplicity(),
+      });
+    }
+  }
+  edges
+}
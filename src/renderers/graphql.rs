@@ -0,0 +1,196 @@
+use super::super::metadata::model::{
+  EdmType, EntityType, EnumType, Model, Multiplicity, Property, PropertyType, Schema,
+};
+use super::Renderer;
+use std::collections::BTreeSet;
+use std::error::Error;
+
+pub struct GraphQlRenderer;
+
+impl Renderer for GraphQlRenderer {
+  fn render(&self, model: &Model) -> Result<String, Box<dyn Error>> {
+    let mut scalars = BTreeSet::new();
+    let mut definitions = Vec::new();
+    for (_, enum_type) in model.enum_types() {
+      definitions.push(Self::render_enum_type(enum_type));
+    }
+    for (_, complex_type) in model.complex_types() {
+      let fields: Vec<_> = complex_type
+        .properties()
+        .iter()
+        .map(|property| Self::render_property(property, false, &mut scalars))
+        .collect();
+      definitions.push(Self::render_object_type(complex_type.name(), &fields));
+    }
+    for (schema, entity_type) in model.entity_types() {
+      let fields = Self::render_entity_fields(model, schema, entity_type, &mut scalars);
+      definitions.push(Self::render_object_type(entity_type.name(), &fields));
+    }
+    let query = Self::render_query(model, &mut scalars);
+    if !query.is_empty() {
+      definitions.push(Self::render_object_type("Query", &query));
+    }
+    let mut graphql: Vec<_> = scalars
+      .iter()
+      .map(|scalar| format!("scalar {}\n", scalar))
+      .collect();
+    graphql.extend(definitions);
+    Ok(graphql.join("\n"))
+  }
+}
+
+impl GraphQlRenderer {
+  fn render_enum_type(enum_type: &EnumType) -> String {
+    let mut graphql = format!("enum {} {{\n", Self::render_name(enum_type.name()));
+    for member in enum_type.members() {
+      let mut value = Self::render_name(member.name());
+      if matches!(value.as_str(), "true" | "false" | "null") {
+        value.push('_');
+      }
+      graphql.push_str(&format!("  {}\n", value));
+    }
+    graphql.push_str("}\n");
+    graphql
+  }
+
+  fn render_object_type(name: &str, fields: &[String]) -> String {
+    let mut graphql = format!("type {} {{\n", Self::render_name(name));
+    for field in fields {
+      graphql.push_str(&format!("  {}\n", field));
+    }
+    graphql.push_str("}\n");
+    graphql
+  }
+
+  fn render_entity_fields(
+    model: &Model,
+    schema: &Schema,
+    entity_type: &EntityType,
+    scalars: &mut BTreeSet<&'static str>,
+  ) -> Vec<String> {
+    let mut fields = Vec::new();
+    for (schema, entity_type) in model.entity_type_hierarchy(schema, entity_type) {
+      for property in entity_type.properties() {
+        let is_key = entity_type.is_key(property.name());
+        fields.push(Self::render_property(property, is_key, scalars));
+      }
+      for navigation in entity_type.navigation_properties() {
+        let target = match model.resolve_navigation(schema, navigation) {
+          Some(target) => target,
+          None => continue,
+        };
+        let target_name = Self::render_name(target.entity_type().name());
+        let type_name = match target.multiplicity() {
+          Multiplicity::Many => format!("[{}!]!", target_name),
+          Multiplicity::One => format!("{}!", target_name),
+          Multiplicity::ZeroOrOne => target_name,
+        };
+        fields.push(format!(
+          "{}: {}",
+          Self::render_name(navigation.name()),
+          type_name
+        ));
+      }
+    }
+    fields
+  }
+
+  fn render_property(
+    property: &Property,
+    is_key: bool,
+    scalars: &mut BTreeSet<&'static str>,
+  ) -> String {
+    let mut type_name = Self::render_property_type(property, scalars);
+    if property.is_collection() {
+      type_name = format!("[{}!]", type_name);
+    }
+    if is_key || !property.facets().nullable() {
+      type_name.push('!');
+    }
+    format!("{}: {}", Self::render_name(property.name()), type_name)
+  }
+
+  fn render_property_type(property: &Property, scalars: &mut BTreeSet<&'static str>) -> String {
+    match property.property_type() {
+      PropertyType::Edm(edm_type) => Self::render_edm_type(*edm_type, scalars).to_owned(),
+      PropertyType::Complex(name) | PropertyType::Enum(name) => {
+        Self::render_name(name.rsplit('.').next().unwrap_or(name))
+      }
+    }
+  }
+
+  fn render_edm_type(edm_type: EdmType, scalars: &mut BTreeSet<&'static str>) -> &'static str {
+    let type_name = match edm_type {
+      EdmType::Guid => "ID",
+      EdmType::Boolean => "Boolean",
+      EdmType::Int16 | EdmType::Int32 | EdmType::Byte | EdmType::SByte => "Int",
+      EdmType::Single | EdmType::Double => "Float",
+      EdmType::String => "String",
+      EdmType::Int64 => "Int64",
+      EdmType::Decimal => "Decimal",
+      EdmType::Date => "Date",
+      EdmType::TimeOfDay | EdmType::Time => "Time",
+      EdmType::DateTime | EdmType::DateTimeOffset => "DateTime",
+      EdmType::Binary | EdmType::Stream => "Binary",
+    };
+    if !matches!(type_name, "ID" | "Boolean" | "Int" | "Float" | "String") {
+      scalars.insert(type_name);
+    }
+    type_name
+  }
+
+  fn render_query(model: &Model, scalars: &mut BTreeSet<&'static str>) -> Vec<String> {
+    let mut fields = Vec::new();
+    for schema in model.schemas() {
+      for entity_set in schema.entity_sets() {
+        let (schema, entity_type) = match model.resolve_entity_type(entity_set.entity_type()) {
+          Some(entity_type) => entity_type,
+          None => continue,
+        };
+        let set_name = Self::render_name(entity_set.name());
+        let type_name = Self::render_name(entity_type.name());
+        fields.push(format!(
+          "{}(top: Int, skip: Int, filter: String): [{}!]!",
+          set_name, type_name
+        ));
+        let keys: Vec<_> = model
+          .entity_type_hierarchy(schema, entity_type)
+          .into_iter()
+          .flat_map(|(_, entity_type)| {
+            entity_type
+              .keys()
+              .iter()
+              .filter_map(|key| entity_type.property(key))
+          })
+          .map(|property| {
+            format!(
+              "{}: {}!",
+              Self::render_name(property.name()),
+              Self::render_property_type(property, scalars)
+            )
+          })
+          .collect();
+        if !keys.is_empty() {
+          fields.push(format!(
+            "{}_by_key({}): {}",
+            set_name,
+            keys.join(", "),
+            type_name
+          ));
+        }
+      }
+    }
+    fields
+  }
+
+  fn render_name(name: &str) -> String {
+    let mut graphql_name: String = name
+      .chars()
+      .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+      .collect();
+    if graphql_name.is_empty() || graphql_name.starts_with(|c: char| c.is_ascii_digit()) {
+      graphql_name.insert(0, '_');
+    }
+    graphql_name
+  }
+}
@@ -0,0 +1,510 @@
+use super::super::metadata::model::{
+  EdmType, EntityType, Facets, Model, Property, PropertyType, Scale, Schema,
+};
+use super::{OutputFile, Renderer};
+use std::collections::HashMap;
+use std::error::Error;
+use std::io;
+use std::path::PathBuf;
+
+pub struct MockDataRenderer {
+  rows: usize,
+  seed: u64,
+}
+
+struct Column {
+  name: String,
+  header: String,
+  property: Property,
+  is_key: bool,
+}
+
+struct Table {
+  path: PathBuf,
+  qualified_name: String,
+  columns: Vec<Column>,
+  rows: Vec<Vec<String>>,
+}
+
+struct Random {
+  state: u64,
+}
+
+impl Random {
+  fn new(seed: u64) -> Self {
+    Random { state: seed }
+  }
+
+  fn next(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+  }
+
+  fn below(&mut self, bound: u64) -> u64 {
+    if bound == 0 {
+      0
+    } else {
+      self.next() % bound
+    }
+  }
+
+  fn between(&mut self, min: i64, max: i64) -> i64 {
+    min + self.below((max - min + 1) as u64) as i64
+  }
+}
+
+const WORDS: &[&str] = &[
+  "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india", "juliet",
+  "kilo", "lima", "mike", "november", "oscar", "papa",
+];
+
+impl Renderer for MockDataRenderer {
+  fn render(&self, _model: &Model) -> Result<String, Box<dyn Error>> {
+    Err(Box::new(io::Error::new(
+      io::ErrorKind::Unsupported,
+      "Mock data consists of one file per entity, render it as files instead",
+    )))
+  }
+
+  fn render_files(&self, model: &Model) -> Result<Vec<OutputFile>, Box<dyn Error>> {
+    let mut random = Random::new(self.seed);
+    let mut tables = model
+      .entity_types()
+      .map(|(schema, entity_type)| self.generate_table(model, schema, entity_type, &mut random))
+      .collect::<Result<Vec<_>, _>>()?;
+    Self::link_foreign_keys(model, &mut tables, &mut random);
+    Ok(
+      tables
+        .iter()
+        .map(|table| OutputFile::new(Some(table.path.clone()), Self::render_csv(table)))
+        .collect(),
+    )
+  }
+}
+
+impl MockDataRenderer {
+  pub fn new(rows: usize, seed: u64) -> Self {
+    MockDataRenderer { rows, seed }
+  }
+
+  fn generate_table(
+    &self,
+    model: &Model,
+    schema: &Schema,
+    entity_type: &EntityType,
+    random: &mut Random,
+  ) -> Result<Table, Box<dyn Error>> {
+    let mut columns = Vec::new();
+    for (_, entity_type) in model.entity_type_hierarchy(schema, entity_type) {
+      for property in entity_type.properties() {
+        let is_key = entity_type.is_key(property.name());
+        let header = property.cds_name().unwrap_or(property.name());
+        Self::add_columns(
+          model,
+          property,
+          property.name(),
+          header,
+          is_key,
+          &mut columns,
+        );
+      }
+    }
+    let key_spaces: Vec<_> = columns
+      .iter()
+      .filter(|column| column.is_key)
+      .map(|column| (column, Self::column_key_space(column)))
+      .collect();
+    let largest_key = key_spaces
+      .iter()
+      .max_by_key(|(_, key_space)| key_space.unwrap_or(u64::MAX));
+    if let Some((column, Some(key_space))) = largest_key {
+      if (self.rows as u64) > *key_space {
+        return Err(Box::new(io::Error::new(
+          io::ErrorKind::InvalidInput,
+          format!(
+            "Cannot generate {} rows of {}, its key {} has only {} distinct values",
+            self.rows,
+            schema.qualify(entity_type.name()),
+            column.name,
+            key_space
+          ),
+        )));
+      }
+    }
+    let rows = (0..self.rows)
+      .map(|index| {
+        columns
+          .iter()
+          .map(|column| Self::generate_value(model, column, index, random))
+          .collect()
+      })
+      .collect();
+    let entity_name = entity_type.cds_name().unwrap_or(entity_type.name());
+    let file_name = if schema.namespace().is_empty() {
+      format!("{}.csv", entity_name)
+    } else {
+      format!("{}-{}.csv", schema.namespace(), entity_name)
+    };
+    Ok(Table {
+      path: ["db", "data", &file_name].iter().collect(),
+      qualified_name: schema.qualify(entity_type.name()),
+      columns,
+      rows,
+    })
+  }
+
+  fn add_columns(
+    model: &Model,
+    property: &Property,
+    name: &str,
+    header: &str,
+    is_key: bool,
+    columns: &mut Vec<Column>,
+  ) {
+    if let (PropertyType::Complex(complex_name), false) =
+      (property.property_type(), property.is_collection())
+    {
+      if let Some((_, complex_type)) = model.find_complex_type(complex_name) {
+        for nested_property in complex_type.properties() {
+          let nested_name = format!("{}_{}", name, nested_property.name());
+          let nested_header = format!(
+            "{}_{}",
+            header,
+            nested_property.cds_name().unwrap_or(nested_property.name())
+          );
+          Self::add_columns(
+            model,
+            nested_property,
+            &nested_name,
+            &nested_header,
+            is_key,
+            columns,
+          );
+        }
+      }
+      return;
+    }
+    columns.push(Column {
+      name: name.to_owned(),
+      header: header.to_owned(),
+      property: property.clone(),
+      is_key,
+    });
+  }
+
+  fn column_key_space(column: &Column) -> Option<u64> {
+    match column.property.property_type() {
+      PropertyType::Edm(edm_type) if !column.property.is_collection() => {
+        Self::key_space(*edm_type, column.property.facets())
+      }
+      _ => None,
+    }
+  }
+
+  fn key_space(edm_type: EdmType, facets: &Facets) -> Option<u64> {
+    match edm_type {
+      EdmType::Boolean => Some(2),
+      EdmType::Byte => Some(u8::MAX as u64),
+      EdmType::SByte => Some(i8::MAX as u64),
+      EdmType::Int16 => Some(i16::MAX as u64),
+      EdmType::Int32 => Some(i32::MAX as u64),
+      EdmType::Decimal => {
+        let (integer_digits, _) = Self::decimal_digits(facets);
+        integer_digits.map(|digits| 10_u64.saturating_pow(digits) - 1)
+      }
+      EdmType::String => facets
+        .max_length()
+        .map(|max_length| 10_u64.saturating_pow(max_length) - 1),
+      EdmType::Binary | EdmType::Stream => facets
+        .max_length()
+        .map(|max_length| 256_u64.saturating_pow(max_length.min(6)) - 1),
+      EdmType::TimeOfDay | EdmType::Time => Some(86_400),
+      _ => None,
+    }
+  }
+
+  fn decimal_digits(facets: &Facets) -> (Option<u32>, u32) {
+    let scale = match facets.scale() {
+      Some(Scale::Fixed(scale)) => scale,
+      _ => 2,
+    };
+    let integer_digits = facets
+      .precision()
+      .map(|precision| precision.saturating_sub(scale));
+    (integer_digits, scale.min(6))
+  }
+
+  fn generate_value(model: &Model, column: &Column, index: usize, random: &mut Random) -> String {
+    let property = &column.property;
+    if property.is_collection() {
+      return String::from("[]");
+    }
+    match property.property_type() {
+      PropertyType::Edm(edm_type) => Self::generate_edm_value(
+        *edm_type,
+        property.facets(),
+        &column.name,
+        column.is_key.then_some(index),
+        random,
+      ),
+      PropertyType::Enum(enum_name) => model
+        .find_enum_type(enum_name)
+        .map(|(_, enum_type)| enum_type.members())
+        .filter(|members| !members.is_empty())
+        .map(|members| {
+          let member = &members[random.below(members.len() as u64) as usize];
+          member.value().to_string()
+        })
+        .unwrap_or_default(),
+      PropertyType::Complex(_) => String::new(),
+    }
+  }
+
+  fn generate_edm_value(
+    edm_type: EdmType,
+    facets: &Facets,
+    name: &str,
+    key_index: Option<usize>,
+    random: &mut Random,
+  ) -> String {
+    let sequence = key_index.map(|index| {
+      let index = index as u64;
+      match Self::key_space(edm_type, facets) {
+        Some(key_space) if key_space > 0 => (index % key_space + 1) as i64,
+        _ => index as i64 + 1,
+      }
+    });
+    match edm_type {
+      EdmType::Guid => {
+        let high = random.next();
+        let low = random.next();
+        format!(
+          "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+          high >> 32,
+          (high >> 16) & 0xFFFF,
+          high & 0x0FFF,
+          0x8000 | (low >> 48) & 0x3FFF,
+          low & 0xFFFF_FFFF_FFFF
+        )
+      }
+      EdmType::Boolean => match sequence {
+        Some(sequence) => (sequence == 2).to_string(),
+        None => (random.below(2) == 1).to_string(),
+      },
+      EdmType::Byte => sequence
+        .unwrap_or_else(|| random.between(0, 255))
+        .to_string(),
+      EdmType::SByte => sequence
+        .unwrap_or_else(|| random.between(-128, 127))
+        .to_string(),
+      EdmType::Int16 => sequence
+        .unwrap_or_else(|| random.between(0, 1_000))
+        .to_string(),
+      EdmType::Int32 => sequence
+        .unwrap_or_else(|| random.between(0, 100_000))
+        .to_string(),
+      EdmType::Int64 => sequence
+        .unwrap_or_else(|| random.between(0, 10_000_000))
+        .to_string(),
+      EdmType::Decimal => {
+        let (integer_digits, scale) = Self::decimal_digits(facets);
+        let integer = match sequence {
+          Some(sequence) => sequence,
+          None => random.between(0, 10_i64.pow(integer_digits.unwrap_or(5).min(6)) - 1),
+        };
+        if scale == 0 {
+          integer.to_string()
+        } else {
+          let fraction = random.between(0, 10_i64.pow(scale) - 1);
+          format!("{}.{:0width$}", integer, fraction, width = scale as usize)
+        }
+      }
+      EdmType::Double | EdmType::Single => match sequence {
+        Some(sequence) => sequence.to_string(),
+        None => format!("{:.2}", random.between(0, 100_000) as f64 / 100.0),
+      },
+      EdmType::Date => Self::format_date(match sequence {
+        Some(sequence) => sequence - 1,
+        None => random.below(1_826) as i64,
+      }),
+      EdmType::TimeOfDay | EdmType::Time => Self::format_time(match sequence {
+        Some(sequence) => sequence as u64 - 1,
+        None => random.below(86_400),
+      }),
+      EdmType::DateTime | EdmType::DateTimeOffset => {
+        let (days, seconds) = match sequence {
+          Some(sequence) => ((sequence - 1) / 86_400, (sequence - 1) as u64 % 86_400),
+          None => (random.below(1_826) as i64, random.below(86_400)),
+        };
+        format!(
+          "{}T{}Z",
+          Self::format_date(days),
+          Self::format_time(seconds)
+        )
+      }
+      EdmType::String => {
+        let max_length = facets.max_length().map(|length| length as usize);
+        match sequence {
+          Some(sequence) => {
+            let value = format!("{}-{}", name, sequence);
+            match max_length {
+              Some(max_length) if value.chars().count() > max_length => sequence.to_string(),
+              _ => value,
+            }
+          }
+          None => {
+            let word = WORDS[random.below(WORDS.len() as u64) as usize];
+            let value = format!("{} {}", name, word);
+            match max_length {
+              Some(max_length) => value.chars().take(max_length).collect(),
+              None => value,
+            }
+          }
+        }
+      }
+      EdmType::Binary | EdmType::Stream => {
+        let bytes: Vec<u8> = match sequence {
+          Some(sequence) => {
+            let length = facets
+              .max_length()
+              .map_or(6, |length| length.min(6) as usize);
+            sequence.to_be_bytes()[8 - length..].to_vec()
+          }
+          None => (0..6).map(|_| random.below(256) as u8).collect(),
+        };
+        Self::encode_base64(&bytes)
+      }
+    }
+  }
+
+  fn format_date(days: i64) -> String {
+    let (year, month, day) = Self::civil_from_days(18_262 + days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+  }
+
+  fn format_time(seconds: u64) -> String {
+    format!(
+      "{:02}:{:02}:{:02}",
+      seconds / 3_600,
+      seconds / 60 % 60,
+      seconds % 60
+    )
+  }
+
+  fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+      (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+      month_index + 3
+    } else {
+      month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+  }
+
+  fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+      let buffer = chunk
+        .iter()
+        .enumerate()
+        .fold(0_u32, |buffer, (index, byte)| {
+          buffer | (u32::from(*byte) << (16 - index * 8))
+        });
+      for index in 0..4 {
+        if index <= chunk.len() {
+          encoded.push(ALPHABET[(buffer >> (18 - index * 6) & 0x3F) as usize] as char);
+        } else {
+          encoded.push('=');
+        }
+      }
+    }
+    encoded
+  }
+
+  fn link_foreign_keys(model: &Model, tables: &mut [Table], random: &mut Random) {
+    let indexes: HashMap<String, usize> = tables
+      .iter()
+      .enumerate()
+      .map(|(index, table)| (table.qualified_name.clone(), index))
+      .collect();
+    for (schema, entity_type) in model.entity_types() {
+      let table_index = indexes[&schema.qualify(entity_type.name())];
+      for foreign_key in model.foreign_keys(schema, entity_type) {
+        let parent_name = foreign_key
+          .referenced_schema()
+          .qualify(foreign_key.referenced_entity_type().name());
+        let parent_index = match indexes.get(&parent_name) {
+          Some(parent_index) => *parent_index,
+          None => continue,
+        };
+        let pairs: Vec<_> = foreign_key
+          .constraints()
+          .iter()
+          .filter_map(|constraint| {
+            let column = Self::column_index(&tables[table_index], constraint.property())?;
+            let parent_column =
+              Self::column_index(&tables[parent_index], constraint.referenced_property())?;
+            Some((column, parent_column))
+          })
+          .collect();
+        if pairs.is_empty() || tables[parent_index].rows.is_empty() {
+          continue;
+        }
+        let is_key = pairs
+          .iter()
+          .any(|(column, _)| tables[table_index].columns[*column].is_key);
+        let parent_rows = tables[parent_index].rows.clone();
+        for (row_index, row) in tables[table_index].rows.iter_mut().enumerate() {
+          let parent_row = if is_key {
+            &parent_rows[row_index % parent_rows.len()]
+          } else {
+            &parent_rows[random.below(parent_rows.len() as u64) as usize]
+          };
+          for (column, parent_column) in pairs.iter() {
+            row[*column] = parent_row[*parent_column].clone();
+          }
+        }
+      }
+    }
+  }
+
+  fn column_index(table: &Table, name: &str) -> Option<usize> {
+    table.columns.iter().position(|column| column.name == name)
+  }
+
+  fn render_csv(table: &Table) -> String {
+    let header: Vec<_> = table
+      .columns
+      .iter()
+      .map(|column| Self::escape(&column.header))
+      .collect();
+    let mut csv = header.join(",");
+    csv.push('\n');
+    for row in table.rows.iter() {
+      let values: Vec<_> = row.iter().map(|value| Self::escape(value)).collect();
+      csv.push_str(&values.join(","));
+      csv.push('\n');
+    }
+    csv
+  }
+
+  fn escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+      format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+      value.to_owned()
+    }
+  }
+}
@@ -0,0 +1,159 @@
+mod cdl;
+mod cds;
+mod csn;
+mod diagram;
+mod graphql;
+mod mock_data;
+mod openapi;
+mod rust;
+mod sql;
+mod typescript;
+
+use super::metadata::model::{Model, ODataVersion};
+use clap::ValueEnum;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+pub use cdl::{cds_identifier, quoted_identifiers, CdlRenderer};
+pub(crate) use cds::facet_arguments;
+pub use csn::CsnRenderer;
+pub use diagram::{DotRenderer, MermaidRenderer};
+pub use graphql::GraphQlRenderer;
+pub use mock_data::MockDataRenderer;
+pub use openapi::OpenApiRenderer;
+pub use rust::RustRenderer;
+pub use sql::{SqlDialect, SqlRenderer};
+pub use typescript::TypeScriptRenderer;
+
+pub trait Renderer {
+  fn render(&self, model: &Model) -> Result<String, Box<dyn Error>>;
+
+  fn render_files(&self, model: &Model) -> Result<Vec<OutputFile>, Box<dyn Error>> {
+    Ok(vec![OutputFile::new(None, self.render(model)?)])
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutputFile {
+  path: Option<PathBuf>,
+  content: String,
+}
+
+impl OutputFile {
+  pub fn new(path: Option<PathBuf>, content: String) -> Self {
+    OutputFile { path, content }
+  }
+
+  pub fn path(&self) -> Option<&Path> {
+    self.path.as_deref()
+  }
+
+  pub fn content(&self) -> &str {
+    &self.content
+  }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+  #[default]
+  Cdl,
+  Csn,
+  #[clap(alias = "ts")]
+  Typescript,
+  Sql,
+  Rust,
+  Openapi,
+  Mermaid,
+  Dot,
+  Graphql,
+  Csv,
+}
+
+#[derive(Clone, Debug)]
+pub struct RenderOptions {
+  dialect: SqlDialect,
+  odata_version: Option<ODataVersion>,
+  rows: usize,
+  seed: u64,
+}
+
+impl Default for RenderOptions {
+  fn default() -> Self {
+    RenderOptions {
+      dialect: SqlDialect::default(),
+      odata_version: None,
+      rows: 10,
+      seed: 0,
+    }
+  }
+}
+
+impl RenderOptions {
+  pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+    self.dialect = dialect;
+    self
+  }
+
+  pub fn with_odata_version(mut self, odata_version: Option<ODataVersion>) -> Self {
+    self.odata_version = odata_version;
+    self
+  }
+
+  pub fn with_rows(mut self, rows: usize) -> Self {
+    self.rows = rows;
+    self
+  }
+
+  pub fn with_seed(mut self, seed: u64) -> Self {
+    self.seed = seed;
+    self
+  }
+
+  pub fn dialect(&self) -> SqlDialect {
+    self.dialect
+  }
+
+  pub fn odata_version(&self) -> Option<ODataVersion> {
+    self.odata_version
+  }
+
+  pub fn rows(&self) -> usize {
+    self.rows
+  }
+
+  pub fn seed(&self) -> u64 {
+    self.seed
+  }
+}
+
+impl Format {
+  pub fn renderer(&self, options: &RenderOptions) -> Box<dyn Renderer> {
+    match self {
+      Format::Cdl => Box::new(CdlRenderer),
+      Format::Csn => Box::new(CsnRenderer),
+      Format::Typescript => Box::new(TypeScriptRenderer),
+      Format::Sql => Box::new(SqlRenderer::new(options.dialect())),
+      Format::Rust => Box::new(RustRenderer::new(options.odata_version())),
+      Format::Openapi => Box::new(OpenApiRenderer::new(options.odata_version())),
+      Format::Mermaid => Box::new(MermaidRenderer),
+      Format::Dot => Box::new(DotRenderer),
+      Format::Graphql => Box::new(GraphQlRenderer),
+      Format::Csv => Box::new(MockDataRenderer::new(options.rows(), options.seed())),
+    }
+  }
+
+  pub fn extension(&self) -> &'static str {
+    match self {
+      Format::Cdl => "cds",
+      Format::Csn => "csn",
+      Format::Typescript => "ts",
+      Format::Sql => "sql",
+      Format::Rust => "rs",
+      Format::Openapi => "openapi.json",
+      Format::Mermaid => "mmd",
+      Format::Dot => "dot",
+      Format::Graphql => "graphql",
+      Format::Csv => "csv",
+    }
+  }
+}
@@ -0,0 +1,600 @@
+use super::super::metadata::model::{
+  ComplexType, EdmType, EntitySet, EntityType, EnumType, Facets, Model, ODataVersion, Operation,
+  OperationImport, OperationKind, Property, PropertyType, Scale, Schema, TypeRef,
+};
+use super::Renderer;
+use serde_json::{json, Map, Value};
+use std::error::Error;
+
+pub struct OpenApiRenderer {
+  version: Option<ODataVersion>,
+}
+
+impl Renderer for OpenApiRenderer {
+  fn render(&self, model: &Model) -> Result<String, Box<dyn Error>> {
+    let version = self
+      .version
+      .or_else(|| model.version())
+      .unwrap_or(ODataVersion::V4);
+    let mut paths = Map::new();
+    let mut schemas = Map::new();
+    for schema in model.schemas() {
+      for entity_set in schema.entity_sets() {
+        Self::render_entity_set_paths(version, model, entity_set, &mut paths);
+      }
+      for operation_import in schema.operation_imports() {
+        Self::render_operation_import_path(version, model, operation_import, &mut paths);
+      }
+    }
+    for (schema, enum_type) in model.enum_types() {
+      schemas.insert(
+        schema.qualify(enum_type.name()),
+        Self::render_enum_type(enum_type),
+      );
+    }
+    for (schema, complex_type) in model.complex_types() {
+      schemas.insert(
+        schema.qualify(complex_type.name()),
+        Self::render_complex_type(model, complex_type),
+      );
+    }
+    for (schema, entity_type) in model.entity_types() {
+      schemas.insert(
+        schema.qualify(entity_type.name()),
+        Self::render_entity_type(model, schema, entity_type),
+      );
+    }
+    let title = model
+      .schemas()
+      .iter()
+      .find_map(|schema| schema.entity_container())
+      .or_else(|| model.schemas().first().map(|schema| schema.namespace()))
+      .unwrap_or("OData Service");
+    let openapi = json!({
+      "openapi": "3.0.3",
+      "info": {
+        "title": title,
+        "version": "1.0.0",
+      },
+      "paths": paths,
+      "components": {
+        "schemas": schemas,
+        "parameters": Self::render_query_parameters(),
+      },
+    });
+    let mut openapi = serde_json::to_string_pretty(&openapi)?;
+    openapi.push('\n');
+    Ok(openapi)
+  }
+}
+
+impl OpenApiRenderer {
+  pub fn new(version: Option<ODataVersion>) -> Self {
+    OpenApiRenderer { version }
+  }
+
+  fn render_entity_set_paths(
+    version: ODataVersion,
+    model: &Model,
+    entity_set: &EntitySet,
+    paths: &mut Map<String, Value>,
+  ) {
+    let (schema, entity_type) = match model.resolve_entity_type(entity_set.entity_type()) {
+      Some(entity_type) => entity_type,
+      None => return,
+    };
+    let set_name = entity_set.name();
+    let reference = Self::render_reference(&schema.qualify(entity_type.name()));
+    paths.insert(
+      format!("/{}", set_name),
+      json!({
+        "get": {
+          "summary": format!("Reads entities from {}", set_name),
+          "tags": [set_name],
+          "parameters": Self::render_query_parameter_references(true),
+          "responses": Self::render_responses(version, "200", &reference, true),
+        },
+        "post": {
+          "summary": format!("Creates a new entity in {}", set_name),
+          "tags": [set_name],
+          "requestBody": Self::render_request_body(&reference),
+          "responses": Self::render_responses(version, "201", &reference, false),
+        },
+      }),
+    );
+
+    let keys = Self::key_properties(model, schema, entity_type);
+    if keys.is_empty() {
+      return;
+    }
+    let key_path = Self::render_key_path(version, &keys);
+    let key_parameters: Vec<_> = keys
+      .iter()
+      .map(|property| {
+        json!({
+          "name": property.name(),
+          "in": "path",
+          "required": true,
+          "schema": Self::render_property_schema(model, property, false),
+        })
+      })
+      .collect();
+    paths.insert(
+      format!("/{}{}", set_name, key_path),
+      json!({
+        "parameters": key_parameters,
+        "get": {
+          "summary": format!("Reads a single entity from {}", set_name),
+          "tags": [set_name],
+          "parameters": [
+            { "$ref": "#/components/parameters/select" },
+          ],
+          "responses": Self::render_responses(version, "200", &reference, false),
+        },
+        "patch": {
+          "summary": format!("Updates a single entity in {}", set_name),
+          "tags": [set_name],
+          "requestBody": Self::render_request_body(&reference),
+          "responses": Self::render_empty_responses(),
+        },
+        "delete": {
+          "summary": format!("Deletes a single entity from {}", set_name),
+          "tags": [set_name],
+          "responses": Self::render_empty_responses(),
+        },
+      }),
+    );
+
+    for navigation in entity_type.navigation_properties() {
+      let target = match model.resolve_navigation(schema, navigation) {
+        Some(target) => target,
+        None => continue,
+      };
+      let target_reference =
+        Self::render_reference(&target.schema().qualify(target.entity_type().name()));
+      let parameters = if target.is_collection() {
+        Self::render_query_parameter_references(true)
+      } else {
+        Self::render_query_parameter_references(false)
+      };
+      paths.insert(
+        format!("/{}{}/{}", set_name, key_path, navigation.name()),
+        json!({
+          "parameters": key_parameters,
+          "get": {
+            "summary": format!("Reads {} of an entity from {}", navigation.name(), set_name),
+            "tags": [set_name],
+            "parameters": parameters,
+            "responses": Self::render_responses(
+              version,
+              "200",
+              &target_reference,
+              target.is_collection()
+            ),
+          },
+        }),
+      );
+    }
+  }
+
+  fn render_operation_import_path(
+    version: ODataVersion,
+    model: &Model,
+    operation_import: &OperationImport,
+    paths: &mut Map<String, Value>,
+  ) {
+    let operation = match model.find_operation(operation_import.operation()) {
+      Some((_, operation)) => operation,
+      None => return,
+    };
+    let tags: Vec<_> = operation_import.entity_set().into_iter().collect();
+    let responses = match operation.return_type() {
+      Some(return_type) => Self::render_responses(
+        version,
+        "200",
+        &Self::render_type_name(model, return_type.name()),
+        return_type.is_collection(),
+      ),
+      None => Self::render_empty_responses(),
+    };
+    let mut path = format!("/{}", operation_import.name());
+    let path_item = if Self::is_read_only(operation) {
+      let in_path = version == ODataVersion::V4 && !operation.parameters().is_empty();
+      if in_path {
+        let parameters: Vec<_> = operation
+          .parameters()
+          .iter()
+          .map(|parameter| {
+            if parameter.type_ref().name() == "Edm.String" {
+              format!("{}='{{{}}}'", parameter.name(), parameter.name())
+            } else {
+              format!("{}={{{}}}", parameter.name(), parameter.name())
+            }
+          })
+          .collect();
+        path.push_str(&format!("({})", parameters.join(",")));
+      }
+      let parameters: Vec<_> = operation
+        .parameters()
+        .iter()
+        .map(|parameter| {
+          json!({
+            "name": parameter.name(),
+            "in": if in_path { "path" } else { "query" },
+            "required": in_path || !parameter.nullable(),
+            "schema": Self::render_type_ref(model, parameter.type_ref()),
+          })
+        })
+        .collect();
+      json!({
+        "get": {
+          "summary": format!("Invokes function {}", operation_import.name()),
+          "tags": tags,
+          "parameters": parameters,
+          "responses": responses,
+        },
+      })
+    } else {
+      let mut properties = Map::new();
+      let mut required = Vec::new();
+      for parameter in operation.parameters() {
+        properties.insert(
+          parameter.name().to_owned(),
+          Self::render_type_ref(model, parameter.type_ref()),
+        );
+        if !parameter.nullable() {
+          required.push(parameter.name());
+        }
+      }
+      let mut body_schema = json!({
+        "type": "object",
+        "properties": properties,
+      });
+      if !required.is_empty() {
+        body_schema["required"] = json!(required);
+      }
+      let method = operation
+        .http_method()
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_else(|| String::from("post"));
+      let mut path_item = Map::new();
+      path_item.insert(
+        method,
+        json!({
+          "summary": format!("Invokes action {}", operation_import.name()),
+          "tags": tags,
+          "requestBody": Self::render_request_body(&body_schema),
+          "responses": responses,
+        }),
+      );
+      Value::Object(path_item)
+    };
+    paths.insert(path, path_item);
+  }
+
+  fn is_read_only(operation: &Operation) -> bool {
+    match operation.http_method() {
+      Some(http_method) => http_method.eq_ignore_ascii_case("GET"),
+      None => operation.kind() == OperationKind::Function,
+    }
+  }
+
+  fn key_properties<'a>(
+    model: &'a Model,
+    schema: &'a Schema,
+    entity_type: &'a EntityType,
+  ) -> Vec<&'a Property> {
+    model
+      .entity_type_hierarchy(schema, entity_type)
+      .into_iter()
+      .flat_map(|(_, entity_type)| {
+        entity_type
+          .keys()
+          .iter()
+          .filter_map(|key| entity_type.property(key))
+      })
+      .collect()
+  }
+
+  fn render_key_path(version: ODataVersion, keys: &[&Property]) -> String {
+    let render_value = |property: &Property| {
+      let quoted = match property.edm_type() {
+        Some(EdmType::String) => true,
+        Some(EdmType::Guid) => version == ODataVersion::V2,
+        _ => false,
+      };
+      match (quoted, property.edm_type()) {
+        (true, Some(EdmType::Guid)) => format!("guid'{{{}}}'", property.name()),
+        (true, _) => format!("'{{{}}}'", property.name()),
+        (false, _) => format!("{{{}}}", property.name()),
+      }
+    };
+    if let [key] = keys {
+      return format!("({})", render_value(key));
+    }
+    let keys: Vec<_> = keys
+      .iter()
+      .map(|key| format!("{}={}", key.name(), render_value(key)))
+      .collect();
+    format!("({})", keys.join(","))
+  }
+
+  fn render_query_parameters() -> Value {
+    json!({
+      "filter": {
+        "name": "$filter",
+        "in": "query",
+        "description": "Filter items by property values",
+        "schema": { "type": "string" },
+      },
+      "select": {
+        "name": "$select",
+        "in": "query",
+        "description": "Select properties to be returned",
+        "explode": false,
+        "schema": { "type": "array", "items": { "type": "string" } },
+      },
+      "orderby": {
+        "name": "$orderby",
+        "in": "query",
+        "description": "Order items by property values",
+        "explode": false,
+        "schema": { "type": "array", "items": { "type": "string" } },
+      },
+      "top": {
+        "name": "$top",
+        "in": "query",
+        "description": "Show only the first n items",
+        "schema": { "type": "integer", "minimum": 0 },
+      },
+      "skip": {
+        "name": "$skip",
+        "in": "query",
+        "description": "Skip the first n items",
+        "schema": { "type": "integer", "minimum": 0 },
+      },
+    })
+  }
+
+  fn render_query_parameter_references(collection: bool) -> Value {
+    let names: &[&str] = if collection {
+      &["filter", "select", "orderby", "top", "skip"]
+    } else {
+      &["select"]
+    };
+    names
+      .iter()
+      .map(|name| json!({ "$ref": format!("#/components/parameters/{}", name) }))
+      .collect()
+  }
+
+  fn render_responses(
+    version: ODataVersion,
+    status: &str,
+    schema: &Value,
+    collection: bool,
+  ) -> Value {
+    let schema = match (version, collection) {
+      (ODataVersion::V4, true) => json!({
+        "type": "object",
+        "properties": { "value": { "type": "array", "items": schema } },
+      }),
+      (ODataVersion::V4, false) => schema.clone(),
+      (ODataVersion::V2, true) => json!({
+        "type": "object",
+        "properties": {
+          "d": {
+            "type": "object",
+            "properties": { "results": { "type": "array", "items": schema } },
+          },
+        },
+      }),
+      (ODataVersion::V2, false) => json!({
+        "type": "object",
+        "properties": { "d": schema },
+      }),
+    };
+    let mut responses = Map::new();
+    responses.insert(
+      status.to_owned(),
+      json!({
+        "description": "Success",
+        "content": { "application/json": { "schema": schema } },
+      }),
+    );
+    responses.insert(String::from("default"), json!({ "description": "Error" }));
+    Value::Object(responses)
+  }
+
+  fn render_empty_responses() -> Value {
+    json!({
+      "204": { "description": "Success" },
+      "default": { "description": "Error" },
+    })
+  }
+
+  fn render_request_body(schema: &Value) -> Value {
+    json!({
+      "required": true,
+      "content": { "application/json": { "schema": schema } },
+    })
+  }
+
+  fn render_enum_type(enum_type: &EnumType) -> Value {
+    let members: Vec<_> = enum_type
+      .members()
+      .iter()
+      .map(|member| member.name())
+      .collect();
+    json!({
+      "type": "string",
+      "enum": members,
+    })
+  }
+
+  fn render_complex_type(model: &Model, complex_type: &ComplexType) -> Value {
+    let object = Self::render_object(
+      model,
+      complex_type
+        .properties()
+        .iter()
+        .map(|property| (property, false)),
+      Map::new(),
+    );
+    let base_type = complex_type
+      .base_type()
+      .and_then(|base_type| model.find_complex_type(base_type))
+      .map(|(schema, base_type)| schema.qualify(base_type.name()));
+    Self::with_base_type(object, base_type)
+  }
+
+  fn render_entity_type(model: &Model, schema: &Schema, entity_type: &EntityType) -> Value {
+    let mut navigations = Map::new();
+    for navigation in entity_type.navigation_properties() {
+      if let Some(target) = model.resolve_navigation(schema, navigation) {
+        let reference =
+          Self::render_reference(&target.schema().qualify(target.entity_type().name()));
+        let navigation_schema = if target.is_collection() {
+          json!({ "type": "array", "items": reference })
+        } else {
+          reference
+        };
+        navigations.insert(navigation.name().to_owned(), navigation_schema);
+      }
+    }
+    let object = Self::render_object(
+      model,
+      entity_type
+        .properties()
+        .map(|property| (property, entity_type.is_key(property.name()))),
+      navigations,
+    );
+    let base_type = model
+      .find_base_type(entity_type)
+      .map(|(schema, base_type)| schema.qualify(base_type.name()));
+    Self::with_base_type(object, base_type)
+  }
+
+  fn render_object<'a>(
+    model: &Model,
+    properties: impl Iterator<Item = (&'a Property, bool)>,
+    navigations: Map<String, Value>,
+  ) -> Value {
+    let mut schema_properties = Map::new();
+    let mut required = Vec::new();
+    for (property, is_key) in properties {
+      let nullable = property.facets().nullable() && !is_key;
+      if !nullable {
+        required.push(property.name().to_owned());
+      }
+      schema_properties.insert(
+        property.name().to_owned(),
+        Self::render_property_schema(model, property, nullable),
+      );
+    }
+    schema_properties.extend(navigations);
+    let mut object = json!({
+      "type": "object",
+      "properties": schema_properties,
+    });
+    if !required.is_empty() {
+      object["required"] = json!(required);
+    }
+    object
+  }
+
+  fn with_base_type(object: Value, base_type: Option<String>) -> Value {
+    match base_type {
+      Some(base_type) => json!({ "allOf": [Self::render_reference(&base_type), object] }),
+      None => object,
+    }
+  }
+
+  fn render_property_schema(model: &Model, property: &Property, nullable: bool) -> Value {
+    let mut schema = match property.property_type() {
+      PropertyType::Edm(edm_type) => Self::render_edm_type(*edm_type, property.facets()),
+      PropertyType::Complex(name) | PropertyType::Enum(name) => {
+        Self::render_named_type(model, name)
+      }
+    };
+    if property.is_collection() {
+      schema = json!({ "type": "array", "items": schema });
+    }
+    if nullable {
+      if schema.get("$ref").is_some() {
+        schema = json!({ "allOf": [schema] });
+      }
+      schema["nullable"] = json!(true);
+    }
+    schema
+  }
+
+  fn render_type_ref(model: &Model, type_ref: &TypeRef) -> Value {
+    let schema = Self::render_type_name(model, type_ref.name());
+    if type_ref.is_collection() {
+      json!({ "type": "array", "items": schema })
+    } else {
+      schema
+    }
+  }
+
+  fn render_type_name(model: &Model, name: &str) -> Value {
+    match EdmType::from_name(name) {
+      Some(edm_type) => Self::render_edm_type(edm_type, &Facets::default()),
+      None => Self::render_named_type(model, name),
+    }
+  }
+
+  fn render_named_type(model: &Model, name: &str) -> Value {
+    let qualified_name = if let Some((schema, enum_type)) = model.find_enum_type(name) {
+      schema.qualify(enum_type.name())
+    } else if let Some((schema, complex_type)) = model.find_complex_type(name) {
+      schema.qualify(complex_type.name())
+    } else if let Some((schema, entity_type)) = model.resolve_entity_type(name) {
+      schema.qualify(entity_type.name())
+    } else {
+      return json!({});
+    };
+    Self::render_reference(&qualified_name)
+  }
+
+  fn render_edm_type(edm_type: EdmType, facets: &Facets) -> Value {
+    let mut schema = match edm_type {
+      EdmType::Guid => json!({ "type": "string", "format": "uuid" }),
+      EdmType::Boolean => json!({ "type": "boolean" }),
+      EdmType::Byte => json!({ "type": "integer", "format": "uint8" }),
+      EdmType::SByte => json!({ "type": "integer", "format": "int8" }),
+      EdmType::Int16 => json!({ "type": "integer", "format": "int16" }),
+      EdmType::Int32 => json!({ "type": "integer", "format": "int32" }),
+      EdmType::Int64 => json!({ "type": "integer", "format": "int64" }),
+      EdmType::Decimal => json!({ "type": "number", "format": "decimal" }),
+      EdmType::Double => json!({ "type": "number", "format": "double" }),
+      EdmType::Single => json!({ "type": "number", "format": "float" }),
+      EdmType::Date => json!({ "type": "string", "format": "date" }),
+      EdmType::TimeOfDay | EdmType::Time => json!({ "type": "string", "format": "time" }),
+      EdmType::DateTime | EdmType::DateTimeOffset => {
+        json!({ "type": "string", "format": "date-time" })
+      }
+      EdmType::String => json!({ "type": "string" }),
+      EdmType::Binary | EdmType::Stream => json!({ "type": "string", "format": "base64url" }),
+    };
+    if let Some(max_length) = facets.max_length() {
+      schema["maxLength"] = json!(max_length);
+    }
+    if edm_type == EdmType::Decimal {
+      if let Some(precision) = facets.precision() {
+        schema["x-precision"] = json!(precision);
+      }
+      match facets.scale() {
+        Some(Scale::Fixed(scale)) => schema["x-scale"] = json!(scale),
+        Some(scale) => schema["x-scale"] = json!(scale.to_string()),
+        None => (),
+      }
+    }
+    schema
+  }
+
+  fn render_reference(qualified_name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", qualified_name) })
+  }
+}
@@ -0,0 +1,416 @@
+use super::super::metadata::model::{
+  ComplexType, EdmType, EntityType, EnumType, Model, NavigationProperty, ODataVersion, Property,
+  PropertyType, Schema,
+};
+use super::Renderer;
+use std::error::Error;
+
+pub struct RustRenderer {
+  version: Option<ODataVersion>,
+}
+
+const V2_HELPERS: &str = r#"pub mod odata_v2 {
+  use serde::de::Error;
+  use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+  fn parse_date(value: &str) -> Option<i64> {
+    let ticks = value.strip_prefix("/Date(")?.strip_suffix(")/")?;
+    let offset = ticks
+      .char_indices()
+      .skip(1)
+      .find(|(_, c)| *c == '+' || *c == '-')
+      .map(|(index, _)| index)
+      .unwrap_or(ticks.len());
+    ticks[..offset].parse().ok()
+  }
+
+  pub mod date {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+      serializer.serialize_str(&format!("/Date({})/", value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+      let value = String::deserialize(deserializer)?;
+      parse_date(&value).ok_or_else(|| D::Error::custom(format!("invalid OData date '{}'", value)))
+    }
+  }
+
+  pub mod date_option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<i64>, serializer: S) -> Result<S::Ok, S::Error> {
+      match value {
+        Some(value) => super::date::serialize(value, serializer),
+        None => serializer.serialize_none(),
+      }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+      match Option::<String>::deserialize(deserializer)? {
+        Some(value) => parse_date(&value)
+          .map(Some)
+          .ok_or_else(|| D::Error::custom(format!("invalid OData date '{}'", value))),
+        None => Ok(None),
+      }
+    }
+  }
+
+  pub mod int64 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+      serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+      String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+    }
+  }
+
+  pub mod int64_option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<i64>, serializer: S) -> Result<S::Ok, S::Error> {
+      match value {
+        Some(value) => super::int64::serialize(value, serializer),
+        None => serializer.serialize_none(),
+      }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+      match Option::<String>::deserialize(deserializer)? {
+        Some(value) => value.parse().map(Some).map_err(D::Error::custom),
+        None => Ok(None),
+      }
+    }
+  }
+
+  pub mod expanded {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Navigation<T> {
+      Deferred {
+        #[allow(dead_code)]
+        __deferred: serde::de::IgnoredAny,
+      },
+      Expanded(T),
+    }
+
+    pub fn serialize<S: Serializer, T: Serialize>(
+      value: &Option<Box<T>>,
+      serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+      value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+      deserializer: D,
+    ) -> Result<Option<Box<T>>, D::Error> {
+      match Option::<Navigation<T>>::deserialize(deserializer)? {
+        Some(Navigation::Expanded(value)) => Ok(Some(Box::new(value))),
+        Some(Navigation::Deferred { .. }) | None => Ok(None),
+      }
+    }
+  }
+
+  pub mod results {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct ResultsRef<'a, T> {
+      results: &'a [T],
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Results<T> {
+      Deferred {
+        #[allow(dead_code)]
+        __deferred: serde::de::IgnoredAny,
+      },
+      Expanded {
+        results: Vec<T>,
+      },
+    }
+
+    pub fn serialize<S: Serializer, T: Serialize>(
+      value: &Option<Vec<T>>,
+      serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+      value
+        .as_ref()
+        .map(|results| ResultsRef { results })
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+      deserializer: D,
+    ) -> Result<Option<Vec<T>>, D::Error> {
+      match Option::<Results<T>>::deserialize(deserializer)? {
+        Some(Results::Expanded { results }) => Ok(Some(results)),
+        Some(Results::Deferred { .. }) | None => Ok(None),
+      }
+    }
+  }
+}
+"#;
+
+const KEYWORDS: &[&str] = &[
+  "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+  "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+  "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type",
+  "unsafe", "use", "where", "while", "yield",
+];
+
+impl Renderer for RustRenderer {
+  fn render(&self, model: &Model) -> Result<String, Box<dyn Error>> {
+    let version = self
+      .version
+      .or_else(|| model.version())
+      .unwrap_or(ODataVersion::V4);
+    let mut items = vec![String::from("use serde::{Deserialize, Serialize};\n")];
+    if version == ODataVersion::V2 {
+      items.push(V2_HELPERS.to_owned());
+    }
+    for (_, enum_type) in model.enum_types() {
+      items.push(Self::render_enum_type(enum_type));
+    }
+    for (_, complex_type) in model.complex_types() {
+      items.push(Self::render_complex_type(version, complex_type));
+    }
+    for (schema, entity_type) in model.entity_types() {
+      items.push(Self::render_entity_type(
+        version,
+        model,
+        schema,
+        entity_type,
+      ));
+    }
+    Ok(items.join("\n"))
+  }
+}
+
+impl RustRenderer {
+  pub fn new(version: Option<ODataVersion>) -> Self {
+    RustRenderer { version }
+  }
+
+  fn render_enum_type(enum_type: &EnumType) -> String {
+    let mut rust =
+      String::from("#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]\n");
+    rust.push_str(&format!(
+      "pub enum {} {{\n",
+      Self::render_type_name(enum_type.name())
+    ));
+    for member in enum_type.members() {
+      let variant = Self::render_type_name(member.name());
+      if variant != member.name() {
+        rust.push_str(&format!("  #[serde(rename = \"{}\")]\n", member.name()));
+      }
+      rust.push_str(&format!("  {},\n", variant));
+    }
+    rust.push_str("}\n");
+    rust
+  }
+
+  fn render_complex_type(version: ODataVersion, complex_type: &ComplexType) -> String {
+    let mut rust = Self::render_struct_head(complex_type.name(), complex_type.base_type());
+    for property in complex_type.properties() {
+      rust.push_str(&Self::render_property(version, property, false));
+    }
+    rust.push_str("}\n");
+    rust
+  }
+
+  fn render_entity_type(
+    version: ODataVersion,
+    model: &Model,
+    schema: &Schema,
+    entity_type: &EntityType,
+  ) -> String {
+    let mut rust = Self::render_struct_head(entity_type.name(), entity_type.base_type());
+    for property in entity_type.properties() {
+      rust.push_str(&Self::render_property(
+        version,
+        property,
+        entity_type.is_key(property.name()),
+      ));
+    }
+    for navigation in entity_type.navigation_properties() {
+      rust.push_str(&Self::render_navigation(version, model, schema, navigation));
+    }
+    rust.push_str("}\n");
+    rust
+  }
+
+  fn render_struct_head(name: &str, base_type: Option<&str>) -> String {
+    let mut rust = String::from("#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]\n");
+    rust.push_str(&format!("pub struct {} {{\n", Self::render_type_name(name)));
+    if let Some(base_type) = base_type {
+      let base_type = base_type.rsplit('.').next().unwrap_or(base_type);
+      rust.push_str(&format!(
+        "  #[serde(flatten)]\n  pub base: {},\n",
+        Self::render_type_name(base_type)
+      ));
+    }
+    rust
+  }
+
+  fn render_property(version: ODataVersion, property: &Property, is_key: bool) -> String {
+    let optional = property.facets().nullable() && !is_key;
+    let mut serde_attributes = Vec::new();
+    let field_name = Self::render_field_name(property.name());
+    if field_name.trim_start_matches("r#") != property.name() {
+      serde_attributes.push(format!("rename = \"{}\"", property.name()));
+    }
+    let mut type_name = match property.property_type() {
+      PropertyType::Edm(edm_type) => Self::render_edm_type(version, *edm_type).to_owned(),
+      PropertyType::Complex(name) | PropertyType::Enum(name) => {
+        Self::render_type_name(name.rsplit('.').next().unwrap_or(name))
+      }
+    };
+    if property.is_collection() {
+      type_name = format!("Vec<{}>", type_name);
+    } else if version == ODataVersion::V2 {
+      let helper = match property.edm_type() {
+        Some(EdmType::DateTime) | Some(EdmType::DateTimeOffset) => Some("date"),
+        Some(EdmType::Int64) => Some("int64"),
+        _ => None,
+      };
+      if let Some(helper) = helper {
+        let suffix = if optional { "_option" } else { "" };
+        serde_attributes.push(format!("with = \"odata_v2::{}{}\"", helper, suffix));
+      }
+    }
+    if optional {
+      type_name = format!("Option<{}>", type_name);
+      serde_attributes.push(String::from("default"));
+    }
+    Self::render_field(&serde_attributes, &field_name, &type_name)
+  }
+
+  fn render_navigation(
+    version: ODataVersion,
+    model: &Model,
+    schema: &Schema,
+    navigation: &NavigationProperty,
+  ) -> String {
+    let target = model.resolve_navigation(schema, navigation);
+    let target_name = target
+      .map(|target| Self::render_type_name(target.entity_type().name()))
+      .unwrap_or_else(|| String::from("serde_json::Value"));
+    let is_collection = target
+      .map(|target| target.is_collection())
+      .unwrap_or_else(|| navigation.is_collection());
+    let mut serde_attributes = Vec::new();
+    let field_name = Self::render_field_name(navigation.name());
+    if field_name.trim_start_matches("r#") != navigation.name() {
+      serde_attributes.push(format!("rename = \"{}\"", navigation.name()));
+    }
+    if version == ODataVersion::V2 {
+      let helper = if is_collection { "results" } else { "expanded" };
+      serde_attributes.push(format!("with = \"odata_v2::{}\"", helper));
+    }
+    serde_attributes.push(String::from("default"));
+    serde_attributes.push(String::from("skip_serializing_if = \"Option::is_none\""));
+    let type_name = if is_collection {
+      format!("Option<Vec<{}>>", target_name)
+    } else {
+      format!("Option<Box<{}>>", target_name)
+    };
+    Self::render_field(&serde_attributes, &field_name, &type_name)
+  }
+
+  fn render_field(serde_attributes: &[String], field_name: &str, type_name: &str) -> String {
+    let mut rust = String::new();
+    if !serde_attributes.is_empty() {
+      rust.push_str(&format!("  #[serde({})]\n", serde_attributes.join(", ")));
+    }
+    rust.push_str(&format!("  pub {}: {},\n", field_name, type_name));
+    rust
+  }
+
+  fn render_edm_type(version: ODataVersion, edm_type: EdmType) -> &'static str {
+    match edm_type {
+      EdmType::Boolean => "bool",
+      EdmType::Byte => "u8",
+      EdmType::SByte => "i8",
+      EdmType::Int16 => "i16",
+      EdmType::Int32 => "i32",
+      EdmType::Int64 => "i64",
+      EdmType::Single => "f32",
+      EdmType::Double => "f64",
+      EdmType::Decimal if version == ODataVersion::V2 => "String",
+      EdmType::Decimal => "f64",
+      EdmType::DateTime | EdmType::DateTimeOffset if version == ODataVersion::V2 => "i64",
+      EdmType::Guid
+      | EdmType::Date
+      | EdmType::TimeOfDay
+      | EdmType::Time
+      | EdmType::DateTime
+      | EdmType::DateTimeOffset
+      | EdmType::String
+      | EdmType::Binary
+      | EdmType::Stream => "String",
+    }
+  }
+
+  fn render_type_name(name: &str) -> String {
+    let mut type_name: String = name
+      .split(|c: char| !c.is_ascii_alphanumeric())
+      .filter(|part| !part.is_empty())
+      .map(|part| {
+        let mut chars = part.chars();
+        match chars.next() {
+          Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+          None => String::new(),
+        }
+      })
+      .collect();
+    if type_name.is_empty() || type_name.starts_with(|c: char| c.is_ascii_digit()) {
+      type_name.insert(0, '_');
+    }
+    type_name
+  }
+
+  fn render_field_name(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut field_name = String::new();
+    for (index, c) in chars.iter().enumerate() {
+      if !c.is_ascii_alphanumeric() {
+        if !field_name.ends_with('_') {
+          field_name.push('_');
+        }
+        continue;
+      }
+      if c.is_ascii_uppercase() && index > 0 && !field_name.ends_with('_') {
+        let previous = chars[index - 1];
+        let next_is_lower = chars
+          .get(index + 1)
+          .map(|next| next.is_ascii_lowercase())
+          .unwrap_or(false);
+        if previous.is_ascii_lowercase()
+          || previous.is_ascii_digit()
+          || (previous.is_ascii_uppercase() && next_is_lower)
+        {
+          field_name.push('_');
+        }
+      }
+      field_name.push(c.to_ascii_lowercase());
+    }
+    let mut field_name = field_name.trim_matches('_').to_owned();
+    if field_name.is_empty() || field_name.starts_with(|c: char| c.is_ascii_digit()) {
+      field_name.insert(0, '_');
+    }
+    if matches!(field_name.as_str(), "self" | "super" | "crate") {
+      field_name.push('_');
+    } else if KEYWORDS.contains(&field_name.as_str()) {
+      field_name.insert_str(0, "r#");
+    }
+    field_name
+  }
+}
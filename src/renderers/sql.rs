@@ -0,0 +1,245 @@
+use super::super::metadata::model::{
+  EdmType, EntityType, Facets, Model, Property, PropertyType, Scale, Schema,
+};
+use super::Renderer;
+use clap::ValueEnum;
+use std::error::Error;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SqlDialect {
+  #[default]
+  Sqlite,
+  #[clap(alias = "postgresql")]
+  Postgres,
+  Hana,
+}
+
+pub struct SqlRenderer {
+  dialect: SqlDialect,
+}
+
+struct Column {
+  name: String,
+  sql_type: String,
+  not_null: bool,
+}
+
+impl Renderer for SqlRenderer {
+  fn render(&self, model: &Model) -> Result<String, Box<dyn Error>> {
+    let mut statements = Vec::new();
+    let mut alter_statements = Vec::new();
+    for (schema, entity_type) in model.entity_types() {
+      let (statement, foreign_keys) = self.render_table(model, schema, entity_type);
+      statements.push(statement);
+      let table_name = Self::quote(&Self::table_name(schema, entity_type));
+      for foreign_key in foreign_keys {
+        alter_statements.push(format!("ALTER TABLE {} ADD {};\n", table_name, foreign_key));
+      }
+    }
+    if !alter_statements.is_empty() {
+      statements.push(alter_statements.concat());
+    }
+    Ok(statements.join("\n"))
+  }
+}
+
+impl SqlRenderer {
+  pub fn new(dialect: SqlDialect) -> Self {
+    SqlRenderer { dialect }
+  }
+
+  fn render_table(
+    &self,
+    model: &Model,
+    schema: &Schema,
+    entity_type: &EntityType,
+  ) -> (String, Vec<String>) {
+    let mut keys = Vec::new();
+    let mut columns = Vec::new();
+    let mut foreign_keys = Vec::new();
+    for (schema, entity_type) in model.entity_type_hierarchy(schema, entity_type) {
+      keys.extend(entity_type.keys().iter().cloned());
+      for property in entity_type.properties() {
+        let is_key = entity_type.is_key(property.name());
+        self.add_columns(model, property, property.name(), is_key, &mut columns);
+      }
+      for foreign_key in model.foreign_keys(schema, entity_type) {
+        let properties: Vec<_> = foreign_key
+          .constraints()
+          .iter()
+          .map(|constraint| Self::quote(constraint.property()))
+          .collect();
+        let referenced_properties: Vec<_> = foreign_key
+          .constraints()
+          .iter()
+          .map(|constraint| Self::quote(constraint.referenced_property()))
+          .collect();
+        foreign_keys.push(format!(
+          "FOREIGN KEY ({}) REFERENCES {} ({})",
+          properties.join(", "),
+          Self::quote(&Self::table_name(
+            foreign_key.referenced_schema(),
+            foreign_key.referenced_entity_type()
+          )),
+          referenced_properties.join(", ")
+        ));
+      }
+    }
+
+    let mut definitions: Vec<_> = columns
+      .iter()
+      .map(|column| {
+        format!(
+          "  {} {}{}",
+          Self::quote(&column.name),
+          column.sql_type,
+          if column.not_null { " NOT NULL" } else { "" }
+        )
+      })
+      .collect();
+    if !keys.is_empty() {
+      let keys: Vec<_> = keys.iter().map(|key| Self::quote(key)).collect();
+      definitions.push(format!("  PRIMARY KEY ({})", keys.join(", ")));
+    }
+    if self.dialect == SqlDialect::Sqlite {
+      let foreign_keys = foreign_keys.drain(..);
+      definitions.extend(foreign_keys.map(|foreign_key| format!("  {}", foreign_key)));
+    }
+    let statement = format!(
+      "CREATE TABLE {} (\n{}\n);\n",
+      Self::quote(&Self::table_name(schema, entity_type)),
+      definitions.join(",\n")
+    );
+    (statement, foreign_keys)
+  }
+
+  fn add_columns(
+    &self,
+    model: &Model,
+    property: &Property,
+    name: &str,
+    not_null: bool,
+    columns: &mut Vec<Column>,
+  ) {
+    let not_null = not_null || !property.facets().nullable();
+    if property.is_collection() {
+      columns.push(Column {
+        name: name.to_owned(),
+        sql_type: self.render_json_type().to_owned(),
+        not_null,
+      });
+      return;
+    }
+    let sql_type = match property.property_type() {
+      PropertyType::Edm(edm_type) => self.render_edm_type(*edm_type, property.facets()),
+      PropertyType::Enum(enum_name) => {
+        let underlying_type = model
+          .find_enum_type(enum_name)
+          .map(|(_, enum_type)| enum_type.underlying_type())
+          .unwrap_or(EdmType::Int32);
+        self.render_edm_type(underlying_type, &Facets::default())
+      }
+      PropertyType::Complex(complex_name) => {
+        if let Some((_, complex_type)) = model.find_complex_type(complex_name) {
+          for nested_property in complex_type.properties() {
+            let nested_name = format!("{}_{}", name, nested_property.name());
+            let mut nested_columns = Vec::new();
+            self.add_columns(
+              model,
+              nested_property,
+              &nested_name,
+              false,
+              &mut nested_columns,
+            );
+            if !not_null {
+              nested_columns
+                .iter_mut()
+                .for_each(|column| column.not_null = false);
+            }
+            columns.extend(nested_columns);
+          }
+        }
+        return;
+      }
+    };
+    columns.push(Column {
+      name: name.to_owned(),
+      sql_type,
+      not_null,
+    });
+  }
+
+  fn render_edm_type(&self, edm_type: EdmType, facets: &Facets) -> String {
+    let dialect = self.dialect;
+    match edm_type {
+      EdmType::Guid => match dialect {
+        SqlDialect::Postgres => String::from("UUID"),
+        _ => String::from("NVARCHAR(36)"),
+      },
+      EdmType::Boolean => String::from("BOOLEAN"),
+      EdmType::Byte => match dialect {
+        SqlDialect::Hana => String::from("TINYINT"),
+        _ => String::from("SMALLINT"),
+      },
+      EdmType::SByte | EdmType::Int16 => String::from("SMALLINT"),
+      EdmType::Int32 => String::from("INTEGER"),
+      EdmType::Int64 => String::from("BIGINT"),
+      EdmType::Decimal => {
+        let name = match dialect {
+          SqlDialect::Postgres => "NUMERIC",
+          _ => "DECIMAL",
+        };
+        match (facets.precision(), facets.scale()) {
+          (Some(precision), Some(Scale::Fixed(scale))) => {
+            format!("{}({}, {})", name, precision, scale)
+          }
+          (Some(precision), None) => format!("{}({})", name, precision),
+          _ => name.to_owned(),
+        }
+      }
+      EdmType::Double => match dialect {
+        SqlDialect::Postgres => String::from("DOUBLE PRECISION"),
+        _ => String::from("DOUBLE"),
+      },
+      EdmType::Single => String::from("REAL"),
+      EdmType::Date => String::from("DATE"),
+      EdmType::TimeOfDay | EdmType::Time => String::from("TIME"),
+      EdmType::DateTime => String::from("TIMESTAMP"),
+      EdmType::DateTimeOffset => match dialect {
+        SqlDialect::Postgres => String::from("TIMESTAMP WITH TIME ZONE"),
+        _ => String::from("TIMESTAMP"),
+      },
+      EdmType::String => match (dialect, facets.max_length()) {
+        (SqlDialect::Postgres, Some(length)) => format!("VARCHAR({})", length),
+        (_, Some(length)) => format!("NVARCHAR({})", length),
+        (SqlDialect::Hana, None) => String::from("NVARCHAR(5000)"),
+        (_, None) => String::from("TEXT"),
+      },
+      EdmType::Binary => match (dialect, facets.max_length()) {
+        (SqlDialect::Postgres, _) => String::from("BYTEA"),
+        (SqlDialect::Hana, Some(length)) => format!("VARBINARY({})", length),
+        _ => String::from("BLOB"),
+      },
+      EdmType::Stream => match dialect {
+        SqlDialect::Postgres => String::from("BYTEA"),
+        _ => String::from("BLOB"),
+      },
+    }
+  }
+
+  fn render_json_type(&self) -> &'static str {
+    match self.dialect {
+      SqlDialect::Sqlite => "TEXT",
+      SqlDialect::Postgres => "JSONB",
+      SqlDialect::Hana => "NCLOB",
+    }
+  }
+
+  fn table_name(schema: &Schema, entity_type: &EntityType) -> String {
+    schema.qualify(entity_type.name()).replace('.', "_")
+  }
+
+  fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+  }
+}
@@ -0,0 +1,153 @@
+use super::super::metadata::model::{
+  ComplexType, EdmType, EntityType, EnumType, Model, NavigationProperty, Property, PropertyType,
+  Schema,
+};
+use super::Renderer;
+use std::error::Error;
+
+pub struct TypeScriptRenderer;
+
+impl Renderer for TypeScriptRenderer {
+  fn render(&self, model: &Model) -> Result<String, Box<dyn Error>> {
+    let mut declarations = Vec::new();
+    for (_, enum_type) in model.enum_types() {
+      declarations.push(Self::render_enum_type(enum_type));
+    }
+    for (_, complex_type) in model.complex_types() {
+      declarations.push(Self::render_complex_type(complex_type));
+    }
+    for (schema, entity_type) in model.entity_types() {
+      declarations.push(Self::render_entity_type(model, schema, entity_type));
+    }
+    Ok(declarations.join("\n"))
+  }
+}
+
+impl TypeScriptRenderer {
+  fn render_enum_type(enum_type: &EnumType) -> String {
+    let members: Vec<_> = enum_type
+      .members()
+      .iter()
+      .map(|member| format!("\"{}\"", member.name()))
+      .collect();
+    let members = if members.is_empty() {
+      String::from("never")
+    } else {
+      members.join(" | ")
+    };
+    format!("export type {} = {};\n", enum_type.name(), members)
+  }
+
+  fn render_complex_type(complex_type: &ComplexType) -> String {
+    let mut ts = Self::render_interface_head(complex_type.name(), complex_type.base_type());
+    for property in complex_type.properties() {
+      ts.push_str(&Self::render_property(property, false));
+    }
+    ts.push_str("}\n");
+    ts
+  }
+
+  fn render_entity_type(model: &Model, schema: &Schema, entity_type: &EntityType) -> String {
+    let mut ts = Self::render_interface_head(entity_type.name(), entity_type.base_type());
+    for property in entity_type.properties() {
+      ts.push_str(&Self::render_property(
+        property,
+        entity_type.is_key(property.name()),
+      ));
+    }
+    for navigation in entity_type.navigation_properties() {
+      ts.push_str(&Self::render_navigation(model, schema, navigation));
+    }
+    ts.push_str("}\n");
+    ts
+  }
+
+  fn render_interface_head(name: &str, base_type: Option<&str>) -> String {
+    match base_type {
+      Some(base_type) => format!(
+        "export interface {} extends {} {{\n",
+        name,
+        base_type.rsplit('.').next().unwrap_or(base_type)
+      ),
+      None => format!("export interface {} {{\n", name),
+    }
+  }
+
+  fn render_property(property: &Property, is_key: bool) -> String {
+    let mut type_name = match property.property_type() {
+      PropertyType::Edm(edm_type) => Self::render_edm_type(*edm_type).to_owned(),
+      PropertyType::Complex(name) | PropertyType::Enum(name) => {
+        name.rsplit('.').next().unwrap_or(name).to_owned()
+      }
+    };
+    if property.is_collection() {
+      return format!(
+        "  {}: {}[];\n",
+        Self::render_name(property.name()),
+        type_name
+      );
+    }
+    let optional = property.facets().nullable() && !is_key;
+    if optional {
+      type_name.push_str(" | null");
+    }
+    format!(
+      "  {}{}: {};\n",
+      Self::render_name(property.name()),
+      if optional { "?" } else { "" },
+      type_name
+    )
+  }
+
+  fn render_navigation(model: &Model, schema: &Schema, navigation: &NavigationProperty) -> String {
+    let type_name = match model.resolve_navigation(schema, navigation) {
+      Some(target) if target.is_collection() => format!("{}[]", target.entity_type().name()),
+      Some(target) => target.entity_type().name().to_owned(),
+      None => String::from("unknown"),
+    };
+    format!(
+      "  {}?: {};\n",
+      Self::render_name(navigation.name()),
+      type_name
+    )
+  }
+
+  fn render_edm_type(edm_type: EdmType) -> &'static str {
+    match edm_type {
+      EdmType::Boolean => "boolean",
+      EdmType::Int16
+      | EdmType::Int32
+      | EdmType::Byte
+      | EdmType::SByte
+      | EdmType::Single
+      | EdmType::Double => "number",
+      EdmType::Guid
+      | EdmType::Int64
+      | EdmType::Decimal
+      | EdmType::Date
+      | EdmType::TimeOfDay
+      | EdmType::Time
+      | EdmType::DateTime
+      | EdmType::DateTimeOffset
+      | EdmType::String
+      | EdmType::Binary
+      | EdmType::Stream => "string",
+    }
+  }
+
+  fn render_name(name: &str) -> String {
+    let is_identifier = name
+      .chars()
+      .next()
+      .map(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+      .unwrap_or(false)
+      && name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+      name.to_owned()
+    } else {
+      format!("\"{}\"", name)
+    }
+  }
+}
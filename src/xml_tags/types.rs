@@ -1,48 +1,67 @@
+use super::super::metadata::ParserError;
 use std::collections::HashMap;
-use std::error;
 use std::fmt;
 
-pub trait TagParser: Iterator<Item = Result<TagEvent, TagError>> {}
+pub trait TagParser: Iterator<Item = Result<TagEvent, ParserError>> {}
 
 pub enum TagEvent {
   Open {
     tag: Tag,
     attributes: HashMap<String, String>,
+    position: Option<Position>,
   },
   Close {
     tag: Tag,
+    position: Option<Position>,
   },
 }
 
-pub enum Tag {
-  Schema,
-  EntityType,
-  Property,
-  NavigationProperty,
-  PropertyRef,
+#[derive(fmt::Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Position {
+  line: u64,
+  column: u64,
 }
 
-#[derive(fmt::Debug, Clone, Hash, PartialEq, Eq)]
-pub struct TagError {
-  message: String,
-}
+impl Position {
+  pub fn new(line: u64, column: u64) -> Position {
+    Position { line, column }
+  }
 
-impl TagError {
-  pub fn new<M: AsRef<str>>(message: M) -> TagError {
-    TagError {
-      message: message.as_ref().to_string(),
-    }
+  pub fn line(&self) -> u64 {
+    self.line
   }
-}
 
-impl fmt::Display for TagError {
-  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(
-      formatter,
-      "Tag Parser Backend Error, reason: {}",
-      self.message
-    )
+  pub fn column(&self) -> u64 {
+    self.column
   }
 }
 
-impl error::Error for TagError {}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tag {
+  Edmx,
+  Schema,
+  EntityType,
+  Property,
+  NavigationProperty,
+  PropertyRef,
+  Annotations,
+  Annotation,
+  Record,
+  PropertyValue,
+  EntityContainer,
+  EntitySet,
+  FunctionImport,
+  ActionImport,
+  Function,
+  Action,
+  Parameter,
+  ReturnType,
+  ComplexType,
+  EnumType,
+  Member,
+  Association,
+  End,
+  ReferentialConstraint,
+  Principal,
+  Dependent,
+}
@@ -1,40 +1,54 @@
+use super::super::metadata::{ParserError, ParserErrorKind};
+use super::types::Position;
 use super::types::Tag;
-use super::types::TagError;
 use super::types::TagEvent;
 use super::types::TagParser;
 
 use std::collections::HashMap;
-use std::error::Error;
-use std::fs::File;
 use std::io::{BufReader, Read};
 use xml::attribute::OwnedAttribute;
-use xml::reader::{EventReader, Events, XmlEvent};
+use xml::common::{Position as XmlPosition, TextPosition};
+use xml::reader::{EventReader, XmlEvent};
 
 pub struct XmlTagParser {
-  tag_parser: Events<BufReader<Box<dyn Read>>>,
+  tag_parser: EventReader<BufReader<Box<dyn Read>>>,
+  finished: bool,
 }
 
 impl Iterator for XmlTagParser {
-  type Item = Result<TagEvent, TagError>;
+  type Item = Result<TagEvent, ParserError>;
 
-  fn next(&mut self) -> Option<Result<TagEvent, TagError>> {
-    loop {
-      match self.tag_parser.next() {
-        Some(Ok(XmlEvent::StartElement {
+  fn next(&mut self) -> Option<Result<TagEvent, ParserError>> {
+    while !self.finished {
+      let event = self.tag_parser.next();
+      let position = Self::build_position(self.tag_parser.position());
+      match event {
+        Ok(XmlEvent::StartElement {
           name, attributes, ..
-        }))
-          if Self::is_supported_tag(&name.local_name) =>
-        {
-          return Some(Ok(Self::build_open_tag_event(name.local_name, &attributes)))
+        }) if Self::is_supported_tag(&name.local_name) => {
+          return Some(Ok(Self::build_open_tag_event(
+            name.local_name,
+            &attributes,
+            position,
+          )))
         }
-        Some(Ok(XmlEvent::EndElement { name })) if Self::is_supported_tag(&name.local_name) => {
-          return Some(Ok(Self::build_close_tag_event(name.local_name)))
+        Ok(XmlEvent::EndElement { name }) if Self::is_supported_tag(&name.local_name) => {
+          return Some(Ok(Self::build_close_tag_event(name.local_name, position)))
+        }
+        Ok(XmlEvent::EndDocument) => self.finished = true,
+        Err(e) => {
+          self.finished = true;
+          let position = Self::build_position(e.position());
+          let error = ParserError::new(ParserErrorKind::XmlSyntax {
+            message: e.msg().to_owned(),
+            source: Some(Box::new(e)),
+          });
+          return Some(Err(error.with_position(Some(position))));
         }
-        Some(Err(e)) => return Some(Err(TagError::new(e.msg()))),
-        None => return None,
         _ => continue,
       }
     }
+    None
   }
 }
 
@@ -44,19 +58,28 @@ impl XmlTagParser {
   pub fn new(reader: Box<dyn Read>) -> Self {
     let file = BufReader::new(reader);
     let tag_parser = EventReader::new(file);
-    let tag_parser = tag_parser.into_iter();
-    XmlTagParser { tag_parser }
+    XmlTagParser {
+      tag_parser,
+      finished: false,
+    }
   }
 
-  pub fn from_file(path: String) -> Result<Self, Box<dyn Error>> {
-    let file = Box::new(File::open(path)?) as Box<dyn Read>;
-    Ok(Self::new(file))
+  fn build_position(position: TextPosition) -> Position {
+    Position::new(position.row + 1, position.column + 1)
   }
 
-  fn build_open_tag_event(name: String, attributes: &[OwnedAttribute]) -> TagEvent {
+  fn build_open_tag_event(
+    name: String,
+    attributes: &[OwnedAttribute],
+    position: Position,
+  ) -> TagEvent {
     let tag = Self::build_tag(name);
     let attributes = Self::parse_attributes(attributes);
-    TagEvent::Open { tag, attributes }
+    TagEvent::Open {
+      tag,
+      attributes,
+      position: Some(position),
+    }
   }
 
   fn parse_attributes(attributes: &[OwnedAttribute]) -> HashMap<String, String> {
@@ -69,26 +92,51 @@ impl XmlTagParser {
     attributes_map
   }
 
-  fn build_close_tag_event(name: String) -> TagEvent {
+  fn build_close_tag_event(name: String, position: Position) -> TagEvent {
     let tag = Self::build_tag(name);
-    TagEvent::Close { tag }
+    TagEvent::Close {
+      tag,
+      position: Some(position),
+    }
   }
 
   fn build_tag(name: String) -> Tag {
-    match name.as_str() {
-      "Schema" => Tag::Schema,
-      "EntityType" => Tag::EntityType,
-      "Property" => Tag::Property,
-      "NavigationProperty" => Tag::NavigationProperty,
-      _ => Tag::PropertyRef,
-    }
+    Self::tag_by_name(&name).unwrap_or(Tag::PropertyRef)
   }
 
   fn is_supported_tag(name: &str) -> bool {
-    name == "Schema"
-      || name == "EntityType"
-      || name == "Property"
-      || name == "PropertyRef"
-      || name == "NavigationProperty"
+    Self::tag_by_name(name).is_some()
+  }
+
+  fn tag_by_name(name: &str) -> Option<Tag> {
+    match name {
+      "Edmx" => Some(Tag::Edmx),
+      "Schema" => Some(Tag::Schema),
+      "EntityType" => Some(Tag::EntityType),
+      "Property" => Some(Tag::Property),
+      "NavigationProperty" => Some(Tag::NavigationProperty),
+      "PropertyRef" => Some(Tag::PropertyRef),
+      "Annotations" => Some(Tag::Annotations),
+      "Annotation" => Some(Tag::Annotation),
+      "Record" => Some(Tag::Record),
+      "PropertyValue" => Some(Tag::PropertyValue),
+      "EntityContainer" => Some(Tag::EntityContainer),
+      "EntitySet" => Some(Tag::EntitySet),
+      "FunctionImport" => Some(Tag::FunctionImport),
+      "ActionImport" => Some(Tag::ActionImport),
+      "Function" => Some(Tag::Function),
+      "Action" => Some(Tag::Action),
+      "Parameter" => Some(Tag::Parameter),
+      "ReturnType" => Some(Tag::ReturnType),
+      "ComplexType" => Some(Tag::ComplexType),
+      "EnumType" => Some(Tag::EnumType),
+      "Member" => Some(Tag::Member),
+      "Association" => Some(Tag::Association),
+      "End" => Some(Tag::End),
+      "ReferentialConstraint" => Some(Tag::ReferentialConstraint),
+      "Principal" => Some(Tag::Principal),
+      "Dependent" => Some(Tag::Dependent),
+      _ => None,
+    }
   }
 }
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+
+pub mod odata_v2 {
+  use serde::de::Error;
+  use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+  fn parse_date(value: &str) -> Option<i64> {
+    let ticks = value.strip_prefix("/Date(")?.strip_suffix(")/")?;
+    let offset = ticks
+      .char_indices()
+      .skip(1)
+      .find(|(_, c)| *c == '+' || *c == '-')
+      .map(|(index, _)| index)
+      .unwrap_or(ticks.len());
+    ticks[..offset].parse().ok()
+  }
+
+  pub mod date {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+      serializer.serialize_str(&format!("/Date({})/", value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+      let value = String::deserialize(deserializer)?;
+      parse_date(&value).ok_or_else(|| D::Error::custom(format!("invalid OData date '{}'", value)))
+    }
+  }
+
+  pub mod date_option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<i64>, serializer: S) -> Result<S::Ok, S::Error> {
+      match value {
+        Some(value) => super::date::serialize(value, serializer),
+        None => serializer.serialize_none(),
+      }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+      match Option::<String>::deserialize(deserializer)? {
+        Some(value) => parse_date(&value)
+          .map(Some)
+          .ok_or_else(|| D::Error::custom(format!("invalid OData date '{}'", value))),
+        None => Ok(None),
+      }
+    }
+  }
+
+  pub mod int64 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+      serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+      String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+    }
+  }
+
+  pub mod int64_option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<i64>, serializer: S) -> Result<S::Ok, S::Error> {
+      match value {
+        Some(value) => super::int64::serialize(value, serializer),
+        None => serializer.serialize_none(),
+      }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+      match Option::<String>::deserialize(deserializer)? {
+        Some(value) => value.parse().map(Some).map_err(D::Error::custom),
+        None => Ok(None),
+      }
+    }
+  }
+
+  pub mod expanded {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Navigation<T> {
+      Deferred {
+        #[allow(dead_code)]
+        __deferred: serde::de::IgnoredAny,
+      },
+      Expanded(T),
+    }
+
+    pub fn serialize<S: Serializer, T: Serialize>(
+      value: &Option<Box<T>>,
+      serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+      value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+      deserializer: D,
+    ) -> Result<Option<Box<T>>, D::Error> {
+      match Option::<Navigation<T>>::deserialize(deserializer)? {
+        Some(Navigation::Expanded(value)) => Ok(Some(Box::new(value))),
+        Some(Navigation::Deferred { .. }) | None => Ok(None),
+      }
+    }
+  }
+
+  pub mod results {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct ResultsRef<'a, T> {
+      results: &'a [T],
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Results<T> {
+      Deferred {
+        #[allow(dead_code)]
+        __deferred: serde::de::IgnoredAny,
+      },
+      Expanded {
+        results: Vec<T>,
+      },
+    }
+
+    pub fn serialize<S: Serializer, T: Serialize>(
+      value: &Option<Vec<T>>,
+      serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+      value
+        .as_ref()
+        .map(|results| ResultsRef { results })
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+      deserializer: D,
+    ) -> Result<Option<Vec<T>>, D::Error> {
+      match Option::<Results<T>>::deserialize(deserializer)? {
+        Some(Results::Expanded { results }) => Ok(Some(results)),
+        Some(Results::Deferred { .. }) | None => Ok(None),
+      }
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Tests {
+  #[serde(rename = "ID")]
+  pub id: String,
+  #[serde(rename = "ParentObjectID", default)]
+  pub parent_object_id: Option<String>,
+  #[serde(rename = "ParentObjectIDLimited", default)]
+  pub parent_object_id_limited: Option<String>,
+  #[serde(rename = "Relationships", with = "odata_v2::results", default, skip_serializing_if = "Option::is_none")]
+  pub relationships: Option<Vec<RelationshipDetail>>,
+  #[serde(rename = "WorkingTimes", with = "odata_v2::expanded", default, skip_serializing_if = "Option::is_none")]
+  pub working_times: Option<Box<serde_json::Value>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelationshipDetail {
+  #[serde(rename = "ObjectID")]
+  pub object_id: String,
+  #[serde(rename = "RelationID")]
+  pub relation_id: String,
+  #[serde(rename = "ParentObjectID")]
+  pub parent_object_id: String,
+  #[serde(rename = "RelationType")]
+  pub relation_type: String,
+  #[serde(rename = "PredecTaskID")]
+  pub predec_task_id: String,
+  #[serde(rename = "SuccTaskID")]
+  pub succ_task_id: String,
+}
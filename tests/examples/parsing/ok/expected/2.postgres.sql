@@ -0,0 +1,27 @@
+CREATE TABLE "Shop_Category" (
+  "ID" INTEGER NOT NULL,
+  "Name" VARCHAR(40) NOT NULL,
+  PRIMARY KEY ("ID")
+);
+
+CREATE TABLE "Shop_Product" (
+  "ID" UUID NOT NULL,
+  "Name" VARCHAR(100),
+  "Price" NUMERIC(10, 2) NOT NULL,
+  "Tags" JSONB,
+  "ReleasedAt" TIMESTAMP WITH TIME ZONE,
+  "Category_ID" INTEGER NOT NULL,
+  PRIMARY KEY ("ID")
+);
+
+CREATE TABLE "Shop_Order" (
+  "OrderNo" BIGINT NOT NULL,
+  "Status" INTEGER NOT NULL,
+  "ShipTo_Street" VARCHAR(60),
+  "ShipTo_City" VARCHAR(40),
+  "Product_ID" UUID,
+  PRIMARY KEY ("OrderNo")
+);
+
+ALTER TABLE "Shop_Product" ADD FOREIGN KEY ("Category_ID") REFERENCES "Shop_Category" ("ID");
+ALTER TABLE "Shop_Order" ADD FOREIGN KEY ("Product_ID") REFERENCES "Shop_Product" ("ID");
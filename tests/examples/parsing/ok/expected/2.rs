@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Status {
+  Open,
+  Shipped,
+  Cancelled,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Address {
+  #[serde(rename = "Street", default)]
+  pub street: Option<String>,
+  #[serde(rename = "City")]
+  pub city: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Category {
+  #[serde(rename = "ID")]
+  pub id: i32,
+  #[serde(rename = "Name")]
+  pub name: String,
+  #[serde(rename = "Products", default, skip_serializing_if = "Option::is_none")]
+  pub products: Option<Vec<Product>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Product {
+  #[serde(rename = "ID")]
+  pub id: String,
+  #[serde(rename = "Name", default)]
+  pub name: Option<String>,
+  #[serde(rename = "Price")]
+  pub price: f64,
+  #[serde(rename = "Tags", default)]
+  pub tags: Option<Vec<String>>,
+  #[serde(rename = "ReleasedAt", default)]
+  pub released_at: Option<String>,
+  #[serde(rename = "Category_ID")]
+  pub category_id: i32,
+  #[serde(rename = "Category", default, skip_serializing_if = "Option::is_none")]
+  pub category: Option<Box<Category>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Order {
+  #[serde(rename = "OrderNo")]
+  pub order_no: i64,
+  #[serde(rename = "Status")]
+  pub status: Status,
+  #[serde(rename = "ShipTo", default)]
+  pub ship_to: Option<Address>,
+  #[serde(rename = "Product_ID", default)]
+  pub product_id: Option<String>,
+  #[serde(rename = "Product", default, skip_serializing_if = "Option::is_none")]
+  pub product: Option<Box<Product>>,
+}
@@ -0,0 +1,26 @@
+CREATE TABLE "Shop_Category" (
+  "ID" INTEGER NOT NULL,
+  "Name" NVARCHAR(40) NOT NULL,
+  PRIMARY KEY ("ID")
+);
+
+CREATE TABLE "Shop_Product" (
+  "ID" NVARCHAR(36) NOT NULL,
+  "Name" NVARCHAR(100),
+  "Price" DECIMAL(10, 2) NOT NULL,
+  "Tags" TEXT,
+  "ReleasedAt" TIMESTAMP,
+  "Category_ID" INTEGER NOT NULL,
+  PRIMARY KEY ("ID"),
+  FOREIGN KEY ("Category_ID") REFERENCES "Shop_Category" ("ID")
+);
+
+CREATE TABLE "Shop_Order" (
+  "OrderNo" BIGINT NOT NULL,
+  "Status" INTEGER NOT NULL,
+  "ShipTo_Street" NVARCHAR(60),
+  "ShipTo_City" NVARCHAR(40),
+  "Product_ID" NVARCHAR(36),
+  PRIMARY KEY ("OrderNo"),
+  FOREIGN KEY ("Product_ID") REFERENCES "Shop_Product" ("ID")
+);
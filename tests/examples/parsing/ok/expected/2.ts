@@ -0,0 +1,30 @@
+export type Status = "Open" | "Shipped" | "Cancelled";
+
+export interface Address {
+  Street?: string | null;
+  City: string;
+}
+
+export interface Category {
+  ID: number;
+  Name: string;
+  Products?: Product[];
+}
+
+export interface Product {
+  ID: string;
+  Name?: string | null;
+  Price: string;
+  Tags: string[];
+  ReleasedAt?: string | null;
+  Category_ID: number;
+  Category?: Category;
+}
+
+export interface Order {
+  OrderNo: string;
+  Status: Status;
+  ShipTo?: Address | null;
+  Product_ID?: string | null;
+  Product?: Product;
+}
@@ -1,11 +1,36 @@
+use cim::parse_file;
 use cim::run;
 use cim::Args;
-use std::fs::read_to_string;
+use cim::CheckArgs;
+use cim::Command;
+use cim::DiffArgs;
+use cim::DiffFormat;
+use cim::ElementCase;
+use cim::Format;
+use cim::ImportArgs;
+use cim::InputArgs;
+use cim::Naming;
+use cim::ParserError;
+use cim::ParserErrorKind;
+use cim::RenderOptions;
+use cim::SqlDialect;
+use cim::ValidationError;
+use std::env::temp_dir;
+use std::error::Error;
+use std::fs::{read_to_string, remove_dir_all};
+use std::io::ErrorKind::NotFound;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
 
 #[test]
 fn with_usual_metadata_it_returns_valid_cds() {
   let args = Args {
-    path: "./tests/examples/parsing/ok/metadata/1.xml".to_string(),
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata/1.xml".to_string()],
+      ..Default::default()
+    },
+    ..Default::default()
   };
 
   let cds = run(args).unwrap();
@@ -16,28 +41,1074 @@ fn with_usual_metadata_it_returns_valid_cds() {
 }
 
 #[test]
-fn with_incorrect_metadata_it_returns_errors() {
-  for test_index in 1..6 {
+fn with_v4_metadata_it_returns_valid_cds() {
+  let args = Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata/2.xml".to_string()],
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  let cds = run(args).unwrap();
+  let valid_cds = read_to_string("./tests/examples/parsing/ok/expected/2.cds").unwrap();
+  let valid_cds = valid_cds.replace("\r\n", "\n"); // For windows compatibility
+
+  assert_eq!(cds, valid_cds);
+}
+
+#[test]
+fn with_csn_format_it_returns_valid_csn() {
+  let args = Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata/1.xml".to_string()],
+      ..Default::default()
+    },
+    format: Format::Csn,
+    ..Default::default()
+  };
+
+  let csn = run(args).unwrap();
+  let valid_csn = read_to_string("./tests/examples/parsing/ok/expected/1.csn").unwrap();
+  let valid_csn = valid_csn.replace("\r\n", "\n"); // For windows compatibility
+
+  assert_eq!(csn, valid_csn);
+  let args = Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata/2.xml".to_string()],
+      ..Default::default()
+    },
+    format: Format::Csn,
+    ..Default::default()
+  };
+  let csn: serde_json::Value = serde_json::from_str(&run(args).unwrap()).unwrap();
+  assert_eq!(
+    csn["definitions"]["Shop.Category"]["elements"]["Products"],
+    serde_json::json!({
+      "type": "cds.Association",
+      "target": "Shop.Product",
+      "cardinality": {"max": "*"},
+      "on": [{"ref": ["Products", "Category"]}, "=", {"ref": ["$self"]}],
+    })
+  );
+  assert_eq!(
+    csn["definitions"]["Shop.Order"]["elements"]["Product"]["on"],
+    serde_json::json!([{"ref": ["Product", "ID"]}, "=", {"ref": ["Product_ID"]}])
+  );
+}
+
+#[test]
+fn with_typescript_format_it_returns_valid_interfaces() {
+  let args = Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata/2.xml".to_string()],
+      ..Default::default()
+    },
+    format: Format::Typescript,
+    ..Default::default()
+  };
+
+  let ts = run(args).unwrap();
+  let valid_ts = read_to_string("./tests/examples/parsing/ok/expected/2.ts").unwrap();
+  let valid_ts = valid_ts.replace("\r\n", "\n"); // For windows compatibility
+
+  assert_eq!(ts, valid_ts);
+}
+
+#[test]
+fn with_sql_format_it_returns_valid_ddl() {
+  let cases = [
+    ("2", SqlDialect::Sqlite, "2.sqlite.sql"),
+    ("2", SqlDialect::Postgres, "2.postgres.sql"),
+    ("2", SqlDialect::Hana, "2.hana.sql"),
+  ];
+  for (metadata, dialect, expected) in cases {
     let args = Args {
-      path: format!("./tests/examples/parsing/error/metadata/{}.xml", test_index),
+      input: InputArgs {
+        paths: vec![format!(
+          "./tests/examples/parsing/ok/metadata/{}.xml",
+          metadata
+        )],
+        ..Default::default()
+      },
+      format: Format::Sql,
+      dialect,
+      ..Default::default()
     };
 
-    if let Err(error) = run(args) {
-      let error_message = error.to_string();
-      let expected_path = format!("./tests/examples/parsing/error/expected/{}.txt", test_index);
-      let expected_message = read_to_string(expected_path).unwrap();
-      assert_eq!(error_message, expected_message);
-      continue;
+    let sql = run(args).unwrap();
+    let valid_sql =
+      read_to_string(format!("./tests/examples/parsing/ok/expected/{}", expected)).unwrap();
+    let valid_sql = valid_sql.replace("\r\n", "\n"); // For windows compatibility
+
+    assert_eq!(sql, valid_sql);
+  }
+  let args = Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/sql/cycles.xml".to_string()],
+      ..Default::default()
+    },
+    format: Format::Sql,
+    dialect: SqlDialect::Postgres,
+    ..Default::default()
+  };
+  let sql = run(args).unwrap();
+  assert!(sql.contains("  \"Salary\" NUMERIC,\n"));
+  assert!(sql.ends_with("ALTER TABLE \"HR_Employee\" ADD FOREIGN KEY (\"Manager_ID\") REFERENCES \"HR_Employee\" (\"ID\");\nALTER TABLE \"HR_Employee\" ADD FOREIGN KEY (\"Department_ID\") REFERENCES \"HR_Department\" (\"ID\");\nALTER TABLE \"HR_Department\" ADD FOREIGN KEY (\"Head_ID\") REFERENCES \"HR_Employee\" (\"ID\");\n"));
+}
+
+#[test]
+fn with_rust_format_it_returns_valid_structs() {
+  for test_index in 1..3 {
+    let args = Args {
+      input: InputArgs {
+        paths: vec![format!(
+          "./tests/examples/parsing/ok/metadata/{}.xml",
+          test_index
+        )],
+        ..Default::default()
+      },
+      format: Format::Rust,
+      ..Default::default()
+    };
+
+    let rust = run(args).unwrap();
+    let valid_rust = read_to_string(format!(
+      "./tests/examples/parsing/ok/expected/{}.rs",
+      test_index
+    ))
+    .unwrap();
+    let valid_rust = valid_rust.replace("\r\n", "\n"); // For windows compatibility
+
+    assert_eq!(rust, valid_rust);
+  }
+}
+
+#[test]
+fn with_openapi_format_it_returns_valid_document() {
+  for test_index in 1..3 {
+    let args = Args {
+      input: InputArgs {
+        paths: vec![format!(
+          "./tests/examples/parsing/ok/metadata/{}.xml",
+          test_index
+        )],
+        ..Default::default()
+      },
+      format: Format::Openapi,
+      ..Default::default()
+    };
+
+    let openapi = run(args).unwrap();
+    let expected_path = format!(
+      "./tests/examples/parsing/ok/expected/{}.openapi.json",
+      test_index
+    );
+    let valid_openapi = read_to_string(expected_path).unwrap();
+    let valid_openapi = valid_openapi.replace("\r\n", "\n"); // For windows compatibility
+
+    assert_eq!(openapi, valid_openapi);
+  }
+}
+
+#[test]
+fn with_diagram_formats_it_returns_valid_diagrams() {
+  for (format, expected) in [(Format::Mermaid, "2.mmd"), (Format::Dot, "2.dot")] {
+    let args = Args {
+      input: InputArgs {
+        paths: vec!["./tests/examples/parsing/ok/metadata/2.xml".to_string()],
+        ..Default::default()
+      },
+      format,
+      ..Default::default()
+    };
+
+    let diagram = run(args).unwrap();
+    let valid_diagram =
+      read_to_string(format!("./tests/examples/parsing/ok/expected/{}", expected)).unwrap();
+    let valid_diagram = valid_diagram.replace("\r\n", "\n"); // For windows compatibility
+
+    assert_eq!(diagram, valid_diagram);
+  }
+}
+
+#[test]
+fn with_graphql_format_it_returns_valid_schema() {
+  let args = Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata/2.xml".to_string()],
+      ..Default::default()
+    },
+    format: Format::Graphql,
+    ..Default::default()
+  };
+
+  let graphql = run(args).unwrap();
+  let valid_graphql = read_to_string("./tests/examples/parsing/ok/expected/2.graphql").unwrap();
+  let valid_graphql = valid_graphql.replace("\r\n", "\n"); // For windows compatibility
+
+  assert_eq!(graphql, valid_graphql);
+}
+
+#[test]
+fn with_csv_format_it_returns_deterministic_mock_data() {
+  let model = parse_file("./tests/examples/parsing/ok/metadata/2.xml".to_string()).unwrap();
+  let options = RenderOptions::default().with_rows(5).with_seed(42);
+
+  let files = Format::Csv.renderer(&options).render_files(&model).unwrap();
+
+  assert_eq!(files.len(), 3);
+  for file in files.iter() {
+    let path = file.path().unwrap();
+    assert!(path.starts_with("db/data"));
+    let file_name = path.file_name().unwrap().to_str().unwrap();
+    let expected_path = format!("./tests/examples/parsing/ok/expected/2.csv/{}", file_name);
+    let valid_csv = read_to_string(expected_path).unwrap();
+    let valid_csv = valid_csv.replace("\r\n", "\n"); // For windows compatibility
+    assert_eq!(file.content(), valid_csv);
+  }
+  let column = |file_name: &str, column: usize| -> Vec<String> {
+    let file = files
+      .iter()
+      .find(|file| file.path().unwrap().ends_with(file_name))
+      .unwrap();
+    file
+      .content()
+      .lines()
+      .skip(1)
+      .map(|line| line.split(',').nth(column).unwrap().to_string())
+      .collect()
+  };
+  let product_ids = column("Shop-Product.csv", 0);
+  let mut unique_ids = product_ids.clone();
+  unique_ids.sort();
+  unique_ids.dedup();
+  assert_eq!(unique_ids.len(), 5);
+  for product_id in column("Shop-Order.csv", 4) {
+    assert!(product_ids.contains(&product_id));
+  }
+  let category_ids = column("Shop-Category.csv", 0);
+  for category_id in column("Shop-Product.csv", 5) {
+    assert!(category_ids.contains(&category_id));
+  }
+
+  let mut model = model;
+  Naming::default()
+    .with_pluralize(true)
+    .with_element_case(Some(ElementCase::Snake))
+    .apply(&mut model)
+    .unwrap();
+  let files = Format::Csv.renderer(&options).render_files(&model).unwrap();
+  let headers: Vec<_> = files
+    .iter()
+    .map(|file| {
+      let path = file.path().unwrap().file_name().unwrap().to_str().unwrap();
+      (path, file.content().lines().next().unwrap())
+    })
+    .collect();
+  assert_eq!(
+    headers,
+    [
+      ("Shop-Categories.csv", "id,name"),
+      (
+        "Shop-Products.csv",
+        "id,name,price,tags,released_at,category_id"
+      ),
+      (
+        "Shop-Orders.csv",
+        "order_no,status,ship_to_street,ship_to_city,product_id"
+      ),
+    ]
+  );
+}
+
+#[test]
+fn with_csv_format_it_keeps_keys_unique_and_within_their_facets() {
+  let model = parse_file("./tests/examples/parsing/mock/keys.xml".to_string()).unwrap();
+  let render = |rows: usize| {
+    let options = RenderOptions::default().with_rows(rows).with_seed(42);
+    Format::Csv.renderer(&options).render_files(&model)
+  };
+
+  let files = render(99).unwrap();
+  let column = |file_name: &str, column: usize| -> Vec<String> {
+    let file = files
+      .iter()
+      .find(|file| file.path().unwrap().ends_with(file_name))
+      .unwrap();
+    file
+      .content()
+      .lines()
+      .skip(1)
+      .map(|line| line.split(',').nth(column).unwrap().to_string())
+      .collect()
+  };
+  let assert_unique = |values: &[String]| {
+    let mut unique = values.to_vec();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), values.len());
+  };
+  let codes = column("Keys-Code.csv", 0);
+  assert_unique(&codes);
+  assert!(codes.iter().all(|code| code.len() <= 2));
+  let amounts = column("Keys-Amount.csv", 0);
+  assert_unique(&amounts);
+  for amount in amounts.iter() {
+    let (integer, fraction) = amount.split_once('.').unwrap();
+    assert!(integer.len() <= 2 && fraction.len() == 2);
+  }
+
+  let error = render(100).unwrap_err();
+  assert_eq!(
+    error.to_string(),
+    "Cannot generate 100 rows of Keys.Code, its key Code has only 99 distinct values"
+  );
+
+  let model = parse_file("./tests/examples/parsing/mock/composite.xml".to_string()).unwrap();
+  let options = RenderOptions::default().with_rows(300).with_seed(42);
+  let files = Format::Csv.renderer(&options).render_files(&model).unwrap();
+  let lines: Vec<_> = files[0].content().lines().skip(1).collect();
+  assert_unique(
+    &lines
+      .iter()
+      .map(|line| line.to_string())
+      .collect::<Vec<_>>(),
+  );
+  for line in lines {
+    let byte: u32 = line.split(',').next().unwrap().parse().unwrap();
+    assert!((1..=255).contains(&byte));
+  }
+}
+
+#[test]
+fn with_output_it_writes_files_and_keeps_existing_ones() {
+  let output_dir = temp_dir().join(format!("cim-output-{}", std::process::id()));
+  let output_file = output_dir.join("model.cds");
+  let args = || Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata/2.xml".to_string()],
+      ..Default::default()
+    },
+    output: Some(output_file.to_str().unwrap().to_string()),
+    ..Default::default()
+  };
+
+  assert_eq!(run(args()).unwrap(), "");
+  let valid_cds = read_to_string("./tests/examples/parsing/ok/expected/2.cds").unwrap();
+  assert_eq!(
+    read_to_string(&output_file).unwrap(),
+    valid_cds.replace("\r\n", "\n")
+  );
+  let error = run(args()).unwrap_err();
+  assert!(error.to_string().contains("already exists"));
+  run(Args {
+    force: true,
+    ..args()
+  })
+  .unwrap();
+
+  let csv_dir = output_dir.join("project");
+  run(Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata/2.xml".to_string()],
+      ..Default::default()
+    },
+    format: Format::Csv,
+    rows: 2,
+    output: Some(csv_dir.to_str().unwrap().to_string()),
+    ..Default::default()
+  })
+  .unwrap();
+  assert!(csv_dir.join("db/data/Shop-Product.csv").is_file());
+
+  remove_dir_all(output_dir).unwrap();
+}
+
+#[test]
+fn with_several_inputs_it_merges_the_models() {
+  let merged = run(Args {
+    input: InputArgs {
+      paths: vec![
+        "./tests/examples/parsing/ok/metadata/1.xml".to_string(),
+        "./tests/examples/parsing/ok/metadata/2.xml".to_string(),
+      ],
+      ..Default::default()
+    },
+    ..Default::default()
+  })
+  .unwrap();
+  for expected in ["1.cds", "2.cds"] {
+    let valid_cds = read_to_string(format!("./tests/examples/parsing/ok/expected/{}", expected))
+      .unwrap()
+      .replace("\r\n", "\n");
+    for definition in valid_cds.split_inclusive("}\n") {
+      assert!(merged.contains(definition));
     }
-    panic!("Missed parsing error");
   }
+
+  let directory = run(Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata".to_string()],
+      ..Default::default()
+    },
+    ..Default::default()
+  })
+  .unwrap();
+  assert_eq!(directory, merged);
+
+  let twice = run(Args {
+    input: InputArgs {
+      paths: vec![
+        "./tests/examples/parsing/ok/metadata/2.xml".to_string(),
+        "./tests/examples/parsing/ok/metadata/2.xml".to_string(),
+      ],
+      ..Default::default()
+    },
+    ..Default::default()
+  })
+  .unwrap();
+  let valid_cds = read_to_string("./tests/examples/parsing/ok/expected/2.cds").unwrap();
+  assert_eq!(twice, valid_cds.replace("\r\n", "\n"));
+}
+
+#[test]
+fn with_split_it_writes_one_output_per_input() {
+  let output_dir = temp_dir().join(format!("cim-split-{}", std::process::id()));
+  run(Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata".to_string()],
+      ..Default::default()
+    },
+    format: Format::Csn,
+    output: Some(output_dir.to_str().unwrap().to_string()),
+    split: true,
+    ..Default::default()
+  })
+  .unwrap();
+  let valid_csn = read_to_string("./tests/examples/parsing/ok/expected/1.csn").unwrap();
+  assert_eq!(
+    read_to_string(output_dir.join("1.csn")).unwrap(),
+    valid_csn.replace("\r\n", "\n")
+  );
+  assert!(output_dir.join("2.csn").is_file());
+
+  run(Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata/2.xml".to_string()],
+      ..Default::default()
+    },
+    format: Format::Csv,
+    output: Some(output_dir.to_str().unwrap().to_string()),
+    split: true,
+    ..Default::default()
+  })
+  .unwrap();
+  assert!(output_dir.join("2/db/data/Shop-Product.csv").is_file());
+
+  remove_dir_all(output_dir).unwrap();
+}
+
+#[test]
+fn with_url_it_fetches_the_metadata_document() {
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let address = listener.local_addr().unwrap();
+  let metadata = read_to_string("./tests/examples/parsing/ok/metadata/2.xml").unwrap();
+  let server = thread::spawn(move || {
+    let mut requests = Vec::new();
+    for stream in listener.incoming().take(2) {
+      let mut stream = stream.unwrap();
+      let mut request = Vec::new();
+      for line in BufReader::new(&stream).lines() {
+        let line = line.unwrap();
+        if line.is_empty() {
+          break;
+        }
+        request.push(line);
+      }
+      let response = if request[0].starts_with("GET /sap/opu/odata/sap/SHOP/$metadata") {
+        format!(
+          "HTTP/1.1 301 Moved Permanently\r\nLocation: http://{}/shop/$metadata?sap-client=100\r\nContent-Length: 0\r\n\r\n",
+          address
+        )
+      } else {
+        format!(
+          "HTTP/1.1 200 OK\r\nContent-Type: application/xml\r\nContent-Length: {}\r\n\r\n{}",
+          metadata.len(),
+          metadata
+        )
+      };
+      stream.write_all(response.as_bytes()).unwrap();
+      requests.push(request);
+    }
+    requests
+  });
+
+  let cds = run(Args {
+    input: InputArgs {
+      urls: vec![format!("http://{}/sap/opu/odata/sap/SHOP/", address)],
+      user: Some("user:secret".to_string()),
+      headers: vec!["X-Requested-With: cim".to_string()],
+      sap_client: Some("100".to_string()),
+      ..Default::default()
+    },
+    ..Default::default()
+  })
+  .unwrap();
+  let valid_cds = read_to_string("./tests/examples/parsing/ok/expected/2.cds").unwrap();
+  assert_eq!(cds, valid_cds.replace("\r\n", "\n"));
+
+  let requests = server.join().unwrap();
+  assert_eq!(
+    requests[0][0],
+    "GET /sap/opu/odata/sap/SHOP/$metadata?sap-client=100 HTTP/1.1"
+  );
+  assert_eq!(
+    requests[1][0],
+    "GET /shop/$metadata?sap-client=100 HTTP/1.1"
+  );
+  for request in requests {
+    assert!(request.contains(&"Authorization: Basic dXNlcjpzZWNyZXQ=".to_string()));
+    assert!(request.contains(&"X-Requested-With: cim".to_string()));
+  }
+}
+
+#[test]
+fn with_url_query_it_appends_metadata_to_the_path() {
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let address = listener.local_addr().unwrap();
+  let metadata = read_to_string("./tests/examples/parsing/ok/metadata/2.xml").unwrap();
+  let server = thread::spawn(move || {
+    let mut requests = Vec::new();
+    for stream in listener.incoming().take(2) {
+      let mut stream = stream.unwrap();
+      let request = BufReader::new(&stream).lines().next().unwrap().unwrap();
+      let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/xml\r\nContent-Length: {}\r\n\r\n{}",
+        metadata.len(),
+        metadata
+      );
+      stream.write_all(response.as_bytes()).unwrap();
+      requests.push(request);
+    }
+    requests
+  });
+
+  for url in [
+    "/sap/opu/odata/sap/SHOP/?sap-client=100",
+    "/sap/opu/odata/sap/SHOP/$metadata?sap-language=EN#top",
+  ] {
+    let cds = run(Args {
+      input: InputArgs {
+        urls: vec![format!("http://{}{}", address, url)],
+        ..Default::default()
+      },
+      ..Default::default()
+    })
+    .unwrap();
+    let valid_cds = read_to_string("./tests/examples/parsing/ok/expected/2.cds").unwrap();
+    assert_eq!(cds, valid_cds.replace("\r\n", "\n"));
+  }
+
+  let requests = server.join().unwrap();
+  assert_eq!(
+    requests,
+    [
+      "GET /sap/opu/odata/sap/SHOP/$metadata?sap-client=100 HTTP/1.1",
+      "GET /sap/opu/odata/sap/SHOP/$metadata?sap-language=EN HTTP/1.1",
+    ]
+  );
+}
+
+#[test]
+fn with_import_it_registers_services_in_the_project() {
+  let project = temp_dir().join(format!("cim-import-{}", std::process::id()));
+  std::fs::create_dir_all(&project).unwrap();
+  std::fs::write(project.join("package.json"), r#"{"name": "shop"}"#).unwrap();
+  let args = || Args {
+    command: Some(Command::Import(ImportArgs {
+      input: InputArgs {
+        paths: vec!["./tests/examples/parsing/ok/metadata".to_string()],
+        ..Default::default()
+      },
+      project: project.to_str().unwrap().to_string(),
+      ..Default::default()
+    })),
+    ..Default::default()
+  };
+
+  assert_eq!(run(args()).unwrap(), "");
+  for service in ["1", "2"] {
+    let valid_cds = read_to_string(format!(
+      "./tests/examples/parsing/ok/expected/{}.cds",
+      service
+    ))
+    .unwrap();
+    let cds = read_to_string(project.join(format!("srv/external/{}.cds", service))).unwrap();
+    assert_eq!(cds, valid_cds.replace("\r\n", "\n"));
+    let metadata = read_to_string(format!(
+      "./tests/examples/parsing/ok/metadata/{}.xml",
+      service
+    ))
+    .unwrap();
+    let edmx = read_to_string(project.join(format!("srv/external/{}.edmx", service))).unwrap();
+    assert_eq!(edmx, metadata);
+  }
+  let package: serde_json::Value =
+    serde_json::from_str(&read_to_string(project.join("package.json")).unwrap()).unwrap();
+  assert_eq!(
+    package,
+    serde_json::json!({
+      "name": "shop",
+      "cds": {
+        "requires": {
+          "1": { "kind": "odata-v2", "model": "srv/external/1" },
+          "2": { "kind": "odata", "model": "srv/external/2" }
+        }
+      }
+    })
+  );
+  let error = run(args()).unwrap_err();
+  assert!(error.to_string().contains("already exists"));
+
+  remove_dir_all(&project).unwrap();
+  std::fs::create_dir_all(&project).unwrap();
+  for (package, key) in [
+    (r#"{"cds": {"requires": true}}"#, "cds.requires"),
+    (r#"{"cds": []}"#, "cds"),
+  ] {
+    std::fs::write(project.join("package.json"), package).unwrap();
+    let error = run(args()).unwrap_err();
+    assert!(error
+      .to_string()
+      .ends_with(&format!("has a key {} that is not a JSON object", key)));
+    assert!(!project.join("srv").exists());
+  }
+
+  remove_dir_all(project).unwrap();
+}
+
+#[test]
+fn with_check_it_fails_on_invalid_metadata() {
+  let args = |path: &str, strict: bool| Args {
+    command: Some(Command::Check(CheckArgs {
+      input: InputArgs {
+        paths: vec![path.to_owned()],
+        ..Default::default()
+      },
+    })),
+    strict,
+    ..Default::default()
+  };
+
+  let error = run(args("./tests/examples/parsing/check/invalid.xml", false)).unwrap_err();
+  let error = error.downcast::<ValidationError>().unwrap();
+  assert_eq!(error.errors(), 5);
+  assert_eq!(error.warnings(), 3);
+  assert_eq!(
+    run(args("./tests/examples/parsing/ok/metadata/2.xml", true)).unwrap(),
+    "1 input(s) checked, 0 warning(s) found\n"
+  );
+  let error = run(args("./tests/examples/parsing/error/metadata/4.xml", false)).unwrap_err();
+  assert_eq!(error.downcast::<ValidationError>().unwrap().errors(), 6);
+  let error = run(args("./tests/examples/parsing/error/metadata/6.xml", false)).unwrap_err();
+  assert!(error.downcast::<ValidationError>().is_err());
+}
+
+#[test]
+fn with_incremental_it_keeps_extensions_and_flags_stale_ones() {
+  let output_dir = temp_dir().join(format!("cim-incremental-{}", std::process::id()));
+  let output_file = output_dir.join("shop.cds");
+  let args = |version: &str, strict: bool| Args {
+    input: InputArgs {
+      paths: vec![format!("./tests/examples/parsing/diff/{}.xml", version)],
+      ..Default::default()
+    },
+    output: Some(output_file.to_str().unwrap().to_string()),
+    incremental: true,
+    strict,
+    ..Default::default()
+  };
+
+  run(args("old", false)).unwrap();
+  let generated = read_to_string(&output_file).unwrap();
+  assert!(generated.contains("entity Product {\n  key ID: UUID;\n  Name: String(100);\n"));
+  let extensions = "\nextend entity Product with {\n  Rating: Integer;\n}\n\nannotate Product with {\n  Rating @title: 'Rating';\n  Tags @UI.Hidden;\n}\n";
+  std::fs::write(&output_file, format!("{}{}", generated, extensions)).unwrap();
+
+  let error = run(args("new", true)).unwrap_err();
+  assert!(error
+    .to_string()
+    .contains("shop.cds:41: annotate Product refers to element Tags that is no longer generated"));
+  run(args("new", false)).unwrap();
+  let regenerated = read_to_string(&output_file).unwrap();
+  assert!(regenerated.contains("entity Product {\n  key ID: UUID;\n  Name: String(60);\n"));
+  assert!(regenerated.ends_with(extensions));
+  assert_eq!(
+    regenerated
+      .matches("// ---- end of generated content ----")
+      .count(),
+    1
+  );
+
+  std::fs::write(&output_file, "entity Product {}\n").unwrap();
+  let error = run(args("new", false)).unwrap_err();
+  assert!(error.to_string().contains("has no generated region"));
+
+  remove_dir_all(output_dir).unwrap();
+}
+
+#[test]
+fn with_diff_it_classifies_changes_between_versions() {
+  let args = |format: DiffFormat| Args {
+    command: Some(Command::Diff(DiffArgs {
+      old: "./tests/examples/parsing/diff/old.xml".to_owned(),
+      new: "./tests/examples/parsing/diff/new.xml".to_owned(),
+      format,
+    })),
+    ..Default::default()
+  };
+
+  let report = run(args(DiffFormat::Text)).unwrap();
+  assert!(report.contains(
+    "breaking: Changed MaxLength of property Name of entity type Shop.Product from 100 to 60\n"
+  ));
+  assert!(report.contains(
+    "compatible: Changed Precision of property Price of entity type Shop.Product from 10 to 12\n"
+  ));
+  assert!(
+    report.contains("breaking: Added non nullable property Sku to entity type Shop.Product\n")
+  );
+  assert!(report.contains("compatible: Added property Description to entity type Shop.Product\n"));
+  assert!(report
+    .contains("breaking: Removed navigation property Products of entity type Shop.Category\n"));
+  assert!(report.contains("breaking: Changed target of navigation property Product of entity type Shop.Order from Shop.Product [0..1] to Shop.Product [*]\n"));
+  assert!(report.ends_with("\n7 breaking and 3 compatible change(s)\n"));
+
+  let report: serde_json::Value =
+    serde_json::from_str(&run(args(DiffFormat::Json)).unwrap()).unwrap();
+  assert_eq!(report["breaking"], 7);
+  assert_eq!(report["compatible"], 3);
+  assert_eq!(report["changes"][3]["kind"], "property-removed");
+  assert_eq!(report["changes"][3]["owner"], "entity type Shop.Product");
+  assert_eq!(report["changes"][3]["element"], "Tags");
+
+  let unchanged = Args {
+    command: Some(Command::Diff(DiffArgs {
+      old: "./tests/examples/parsing/diff/old.xml".to_owned(),
+      new: "./tests/examples/parsing/diff/old.xml".to_owned(),
+      format: DiffFormat::Text,
+    })),
+    ..Default::default()
+  };
+  assert_eq!(run(unchanged).unwrap(), "No changes\n");
+}
+
+#[test]
+fn with_config_it_overrides_the_type_mapping() {
+  let args = |config: &str, format: Format| Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata/2.xml".to_string()],
+      ..Default::default()
+    },
+    format,
+    config: Some(format!("./tests/examples/config/{}", config)),
+    ..Default::default()
+  };
+
+  let cds = run(args("cim.toml", Format::Cdl)).unwrap();
+  assert!(cds.contains("entity Category {\n  key ID: Int32;\n  Name: String(40);\n"));
+  assert!(cds.contains("type Address {\n  Street: String(60);\n  City: String(40);\n}\n"));
+  assert!(cds.contains("  Name: LargeString;\n  Price: Decimal(15, 3);\n  Tags: many LargeString;\n  ReleasedAt: DateTime @odata.Type: 'Edm.DateTimeOffset' @odata.Precision: 7;\n  Category_ID: Int32;\n"));
+  assert!(cds.contains("  Status: OrderStatus;\n"));
+  assert!(cds.contains("  Product_ID: String(36);\n"));
+  let csn: serde_json::Value =
+    serde_json::from_str(&run(args("cim.toml", Format::Csn)).unwrap()).unwrap();
+  assert_eq!(
+    csn["definitions"]["Shop.Product"]["elements"]["Price"],
+    serde_json::json!({"type": "cds.Decimal", "precision": 15, "scale": 3})
+  );
+  assert_eq!(
+    csn["definitions"]["Shop.Category"]["elements"]["Name"],
+    serde_json::json!({"type": "cds.String", "length": 40})
+  );
+  assert_eq!(
+    csn["definitions"]["Shop.Product"]["elements"]["ReleasedAt"],
+    serde_json::json!({
+      "type": "cds.DateTime",
+      "@odata.Type": "Edm.DateTimeOffset",
+      "@odata.Precision": 7,
+    })
+  );
+  assert_eq!(
+    csn["definitions"]["Shop.Product"]["elements"]["Tags"],
+    serde_json::json!({"items": {"type": "cds.LargeString"}})
+  );
+  assert_eq!(
+    csn["definitions"]["Shop.Order"]["elements"]["Status"],
+    serde_json::json!({"type": "OrderStatus"})
+  );
+  let cds = run(args("cim.json", Format::Cdl)).unwrap();
+  assert!(cds.contains("  key OrderNo: Int64;\n"));
+  assert!(cds.contains("  Name: String(80);\n"));
+  let error = run(args("invalid.toml", Format::Cdl)).unwrap_err();
+  assert!(error
+    .to_string()
+    .ends_with("invalid.toml, unknown Edm type 'Geography'"));
+  let error = run(args("invalid_type.toml", Format::Cdl)).unwrap_err();
+  assert!(error
+    .to_string()
+    .ends_with("invalid_type.toml, invalid CDS type 'Decimal(15, 3'"));
+}
+
+#[test]
+fn with_naming_options_it_renames_and_keeps_original_names() {
+  let args = |strip_suffix: &str, format: Format| Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/naming/API_BUSINESS_PARTNER.xml".to_string()],
+      ..Default::default()
+    },
+    format,
+    strip_prefix: vec!["A_".to_string()],
+    strip_suffix: vec![strip_suffix.to_string(), "Type".to_string()],
+    pluralize: true,
+    element_case: Some(ElementCase::Camel),
+    ..Default::default()
+  };
+
+  let cds = run(args("Type", Format::Cdl)).unwrap();
+  assert!(cds.starts_with("@odata.name: 'A_BusinessPartnerType'\nentity BusinessPartners {\n  @odata.name: 'BusinessPartner'\n  key businessPartner: String(10);\n  @odata.name: 'BusinessPartnerFullName'\n  businessPartnerFullName: String(81);\n"));
+  assert!(cds.contains("  @odata.name: 'to_BusinessPartnerAddress'\n  toBusinessPartnerAddress: Association to BusinessPartnerAddresses on ...;\n"));
+  assert!(cds.contains("  @odata.name: 'AddressID'\n  key addressId: String(10);\n"));
+  let csn: serde_json::Value =
+    serde_json::from_str(&run(args("Type", Format::Csn)).unwrap()).unwrap();
+  let partners = &csn["definitions"]["API_BUSINESS_PARTNER.BusinessPartners"];
+  assert_eq!(partners["@odata.name"], "A_BusinessPartnerType");
+  assert_eq!(
+    partners["elements"]["toBusinessPartnerAddress"],
+    serde_json::json!({
+      "@odata.name": "to_BusinessPartnerAddress",
+      "type": "cds.Association",
+      "target": "API_BUSINESS_PARTNER.BusinessPartnerAddresses",
+      "cardinality": {"max": "*"},
+    })
+  );
+  let error = run(args("AddressType", Format::Cdl)).unwrap_err();
+  assert_eq!(
+    error.to_string(),
+    "Naming options map the entity types API_BUSINESS_PARTNER.A_BusinessPartnerType and API_BUSINESS_PARTNER.A_BusinessPartnerAddressType to the same name BusinessPartners"
+  );
+
+  let csn: serde_json::Value = serde_json::from_str(
+    &run(Args {
+      input: InputArgs {
+        paths: vec!["./tests/examples/naming/namespaces.xml".to_string()],
+        ..Default::default()
+      },
+      format: Format::Csn,
+      pluralize: true,
+      ..Default::default()
+    })
+    .unwrap(),
+  )
+  .unwrap();
+  assert_eq!(csn["definitions"]["A.Products"]["@odata.name"], "Product");
+  assert_eq!(csn["definitions"]["B.Products"]["@odata.name"], "Product");
+  for format in [
+    Format::Typescript,
+    Format::Sql,
+    Format::Graphql,
+    Format::Rust,
+  ] {
+    let error = run(args("Type", format)).unwrap_err();
+    assert!(error
+      .to_string()
+      .ends_with("only apply to --format cdl, csn and csv"));
+  }
+}
+
+#[test]
+fn with_value_lists_it_escapes_strings_and_matches_the_full_target() {
+  let args = |format: Format, strict: bool| Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/value_lists/metadata.xml".to_string()],
+      ..Default::default()
+    },
+    format,
+    strict,
+    ..Default::default()
+  };
+
+  let cds = run(args(Format::Cdl, false)).unwrap();
+  let (sales, billing) = cds.split_at(cds.rfind("entity Customer").unwrap());
+  assert!(sales.contains("  @Common.ValueList: {\n    CollectionPath: 'Customers',\n"));
+  assert!(
+    sales.contains("  @Common.ValueList #ByRegion: {\n    CollectionPath: 'CustomersByRegion',\n")
+  );
+  assert!(sales.contains("        Constant: 'O''Higgins'\n"));
+  assert!(!sales.contains("Payers"));
+  assert!(sales.contains("    CollectionPath: 'Regions',\n    Parameters: [\n      {\n        $Type: 'Common.ValueListParameterOut',\n        LocalDataProperty: Region,\n"));
+  assert!(!cds.contains("Missing"));
+  assert!(billing.contains("    CollectionPath: 'Payers',\n    Label: 'Customer''s ID',\n"));
+  assert!(!billing.contains("Customers"));
+  let csn: serde_json::Value =
+    serde_json::from_str(&run(args(Format::Csn, false)).unwrap()).unwrap();
+  let id = &csn["definitions"]["Sales.Customer"]["elements"]["ID"];
+  assert_eq!(id["@Common.ValueList"]["CollectionPath"], "Customers");
+  assert_eq!(
+    id["@Common.ValueList#ByRegion"]["CollectionPath"],
+    "CustomersByRegion"
+  );
+  let id = &csn["definitions"]["Billing.Customer"]["elements"]["ID"];
+  assert_eq!(id["@Common.ValueList"]["Label"], "Customer's ID");
+  let error = run(args(Format::Cdl, true)).unwrap_err();
+  assert!(error
+    .to_string()
+    .contains("Unknown target 'Sales.Missing/ID' of a value list annotation"));
+}
+
+#[test]
+fn with_entity_filters_it_keeps_a_consistent_subset() {
+  let args = |include: &[&str], reachable_from: &[&str], depth: Option<usize>| Args {
+    input: InputArgs {
+      paths: vec!["./tests/examples/parsing/ok/metadata/2.xml".to_string()],
+      ..Default::default()
+    },
+    include: include.iter().map(|pattern| pattern.to_string()).collect(),
+    exclude: vec!["Cat*".to_string()],
+    reachable_from: reachable_from.iter().map(|name| name.to_string()).collect(),
+    depth,
+    ..Default::default()
+  };
+
+  let cds = run(args(&["Shop.Prod*", "Order"], &[], None)).unwrap();
+  assert!(!cds.contains("entity Category"));
+  assert!(cds.contains("  Category_ID: Integer;\n}\n"));
+  assert!(cds.contains("entity ![Order] {"));
+  let cds = run(args(&[], &["Order"], Some(0))).unwrap();
+  assert!(!cds.contains("entity Product"));
+  assert!(cds.starts_with("type Status : Integer enum {"));
+  assert!(cds.contains("entity ![Order] {\n  key OrderNo: Integer64;\n  Status: Status;\n  ShipTo: Address;\n  Product_ID: UUID;\n}\n"));
+  let cds = run(args(&[], &["Order"], Some(1))).unwrap();
+  assert!(cds.contains("entity Product {"));
+  assert!(cds.contains("  Product: Association to Product on ...;\n"));
+  let cds = run(args(&["Product"], &[], None)).unwrap();
+  assert!(!cds.contains("type Status"));
+  assert!(!cds.contains("type Address"));
+  let error = run(args(&[], &["Shop.Unknown"], None)).unwrap_err();
+  assert_eq!(
+    error.to_string(),
+    "Unknown entity type 'Shop.Unknown' passed to --reachable-from"
+  );
+}
+
+#[test]
+fn with_incorrect_metadata_it_returns_errors() {
+  let parse_error = |test_index: usize| {
+    let path = format!("./tests/examples/parsing/error/metadata/{}.xml", test_index);
+    *parse_file(path)
+      .unwrap_err()
+      .downcast::<ParserError>()
+      .unwrap()
+  };
+  let is_missing = |error: &ParserError, expected_element: &str, expected_attribute: &str| {
+    matches!(
+      error.kind(),
+      ParserErrorKind::MissingAttribute { element, attribute }
+        if element == expected_element && attribute == expected_attribute
+    )
+  };
+
+  let error = parse_error(1);
+  assert!(is_missing(&error, "EntityType", "Name"));
+  assert_eq!((error.line(), error.column()), (Some(10), Some(4)));
+  let error = parse_error(2);
+  assert!(is_missing(&error, "Schema", "Namespace"));
+  assert_eq!(error.line(), Some(7));
+  let error = parse_error(3);
+  assert!(is_missing(&error, "NavigationProperty", "ToRole"));
+  assert_eq!(error.context(), Some("entity type Tests"));
+  let error = parse_error(4);
+  assert!(is_missing(&error, "Property", "Name"));
+  assert_eq!(error.context(), Some("entity type RelationshipDetail"));
+  let error = parse_error(5);
+  assert!(matches!(
+    error.kind(),
+    ParserErrorKind::UnknownType { name } if name == "Edm.GeographyPolygon"
+  ));
+  assert_eq!(
+    error.context(),
+    Some("entity type Tests, property ParentObjectID")
+  );
+  let error = parse_error(6);
+  assert!(matches!(error.kind(), ParserErrorKind::XmlSyntax { .. }));
+  assert_eq!((error.line(), error.column()), (Some(14), Some(60)));
+  assert!(error.source().is_some());
+  assert_eq!(
+    error.to_string(),
+    "Metadata Parser Error, reason: Invalid XML, Unexpected token inside opening tag: </\n  --> ./tests/examples/parsing/error/metadata/6.xml:14:60\n   |\n14 | <Property Name=\"ID\" Type=\"Edm.String\" Nullable=\"false\" </EntityType>\n   |                                                        ^\n   = note: in entity type Tests"
+  );
+
+  let error = *parse_file("./invldpath.xml".to_string())
+    .unwrap_err()
+    .downcast::<ParserError>()
+    .unwrap();
+  assert!(matches!(error.kind(), ParserErrorKind::Io { source } if source.kind() == NotFound));
+  assert_eq!(error.file(), Some("./invldpath.xml"));
+
+  for test_index in 1..7 {
+    let error = run(Args {
+      input: InputArgs {
+        paths: vec![format!(
+          "./tests/examples/parsing/error/metadata/{}.xml",
+          test_index
+        )],
+        ..Default::default()
+      },
+      strict: true,
+      ..Default::default()
+    })
+    .unwrap_err();
+    assert_eq!(
+      error.downcast::<ParserError>().unwrap().to_string(),
+      parse_error(test_index).to_string()
+    );
+  }
+}
+
+#[test]
+fn with_incorrect_metadata_it_skips_unsupported_constructs_unless_strict() {
+  let args = |test_index: usize| Args {
+    input: InputArgs {
+      paths: vec![format!(
+        "./tests/examples/parsing/error/metadata/{}.xml",
+        test_index
+      )],
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  let cds = run(args(1)).unwrap();
+  assert!(!cds.contains("entity Tests"));
+  assert!(cds.contains("entity RelationshipDetail {"));
+  let cds = run(args(4)).unwrap();
+  assert!(!cds.contains("RelationID"));
+  let cds = run(args(5)).unwrap();
+  assert!(cds.contains("  ParentObjectID: LargeString @odata.Type: 'Edm.GeographyPolygon';\n"));
+  assert!(cds.contains("  ParentObjectIDLimited: String(10);\n"));
+  assert!(run(args(6)).is_err());
 }
 
 #[test]
 #[should_panic]
 fn with_missing_file_it_returns_error() {
   let args = Args {
-    path: "./invldpath.xml".to_string(),
+    input: InputArgs {
+      paths: vec!["./invldpath.xml".to_string()],
+      ..Default::default()
+    },
+    ..Default::default()
   };
   run(args).unwrap();
 }